@@ -1 +1,4 @@
+pub mod gradient;
+pub mod label_layout;
+pub mod minimap;
 pub mod path2d;
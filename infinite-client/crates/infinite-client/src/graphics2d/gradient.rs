@@ -0,0 +1,228 @@
+use crate::utils::color::Color;
+use std::f64::consts::TAU;
+use xross_core::{XrossClass, xross_methods};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientKind {
+    Linear {
+        x0: f64,
+        y0: f64,
+        x1: f64,
+        y1: f64,
+    },
+    Radial {
+        x0: f64,
+        y0: f64,
+        r0: f64,
+        x1: f64,
+        y1: f64,
+        r1: f64,
+    },
+    /// `start_angle` is measured clockwise from the positive x axis, like Canvas.
+    Conic {
+        start_angle: f64,
+        x: f64,
+        y: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    pub offset: f64,
+    pub color: Color,
+}
+
+/// A gradient paint following the Canvas `CanvasGradient` rules: stops are kept sorted
+/// by offset (equal offsets in insertion order), positions outside the stop range use
+/// the nearest stop and degenerate gradients paint transparent black.
+#[derive(XrossClass, Clone, Debug)]
+#[xross(clonable)]
+pub struct Gradient {
+    kind: GradientKind,
+    stops: Vec<ColorStop>,
+}
+
+impl Default for Gradient {
+    fn default() -> Self {
+        Self {
+            kind: GradientKind::Linear {
+                x0: 0.0,
+                y0: 0.0,
+                x1: 0.0,
+                y1: 0.0,
+            },
+            stops: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl Gradient {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn set_linear(&mut self, x0: f64, y0: f64, x1: f64, y1: f64) {
+        self.kind = GradientKind::Linear { x0, y0, x1, y1 };
+    }
+
+    /// Returns `false` and keeps the previous gradient for negative or non-finite
+    /// radii, where Canvas throws an `IndexSizeError`.
+    #[xross_method(critical)]
+    pub fn set_radial(&mut self, x0: f64, y0: f64, r0: f64, x1: f64, y1: f64, r1: f64) -> bool {
+        if !(r0.is_finite() && r1.is_finite()) || r0 < 0.0 || r1 < 0.0 {
+            return false;
+        }
+        self.kind = GradientKind::Radial {
+            x0,
+            y0,
+            r0,
+            x1,
+            y1,
+            r1,
+        };
+        true
+    }
+
+    #[xross_method(critical)]
+    pub fn set_conic(&mut self, start_angle: f64, x: f64, y: f64) {
+        self.kind = GradientKind::Conic { start_angle, x, y };
+    }
+
+    /// Offsets outside `0.0..=1.0` are ignored, matching the `IndexSizeError` in Canvas.
+    #[xross_method(critical)]
+    pub fn add_color_stop(&mut self, offset: f64, color_raw: i32) {
+        if !(0.0..=1.0).contains(&offset) {
+            return;
+        }
+        let index = self.stops.partition_point(|s| s.offset <= offset);
+        self.stops.insert(
+            index,
+            ColorStop {
+                offset,
+                color: Color::from_raw(color_raw),
+            },
+        );
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_color_stops(&mut self) {
+        self.stops.clear();
+    }
+
+    #[xross_method(critical)]
+    pub fn color_at(&self, x: f64, y: f64) -> i32 {
+        self.sample(x, y).into_raw()
+    }
+}
+
+impl Gradient {
+    pub fn kind(&self) -> GradientKind {
+        self.kind
+    }
+
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    pub fn sample(&self, x: f64, y: f64) -> Color {
+        match self.position(x, y) {
+            Some(t) => self.color_at_offset(t),
+            None => Color::default(),
+        }
+    }
+
+    /// Gradient parameter of a point, `None` where the gradient paints nothing.
+    pub fn position(&self, x: f64, y: f64) -> Option<f64> {
+        match self.kind {
+            GradientKind::Linear { x0, y0, x1, y1 } => {
+                let (dx, dy) = (x1 - x0, y1 - y0);
+                let len_sq = dx * dx + dy * dy;
+                if len_sq == 0.0 {
+                    return None;
+                }
+                Some(((x - x0) * dx + (y - y0) * dy) / len_sq)
+            }
+            GradientKind::Radial {
+                x0,
+                y0,
+                r0,
+                x1,
+                y1,
+                r1,
+            } => radial_position(x - x0, y - y0, x1 - x0, y1 - y0, r0, r1),
+            GradientKind::Conic {
+                start_angle,
+                x: cx,
+                y: cy,
+            } => {
+                let angle = (y - cy).atan2(x - cx) - start_angle;
+                Some((angle / TAU).rem_euclid(1.0))
+            }
+        }
+    }
+
+    pub fn color_at_offset(&self, t: f64) -> Color {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::default();
+        };
+        if t <= first.offset {
+            return first.color;
+        }
+        if t >= last.offset {
+            return last.color;
+        }
+        // First stop strictly after `t`; the one before it is the last stop at or before `t`.
+        let next = self.stops.partition_point(|s| s.offset <= t);
+        let (a, b) = (&self.stops[next - 1], &self.stops[next]);
+        let range = b.offset - a.offset;
+        if range <= 0.0 {
+            return b.color;
+        }
+        a.color.mix(b.color, ((t - a.offset) / range) as f32)
+    }
+}
+
+/// Solves the two-circle cone from the Canvas spec for the largest `ω` whose circle
+/// passes through the point and has a non-negative radius.
+/// `(px, py)` and `(cx, cy)` are relative to the start circle center.
+/// Negative radii are invalid; identical circles and two zero radii are degenerate.
+/// Neither paints anything.
+fn radial_position(px: f64, py: f64, cx: f64, cy: f64, r0: f64, r1: f64) -> Option<f64> {
+    if r0 < 0.0 || r1 < 0.0 {
+        return None;
+    }
+    let dr = r1 - r0;
+    if (cx == 0.0 && cy == 0.0 && dr == 0.0) || (r0 == 0.0 && r1 == 0.0) {
+        return None;
+    }
+    let a = cx * cx + cy * cy - dr * dr;
+    let b = px * cx + py * cy + r0 * dr;
+    let c = px * px + py * py - r0 * r0;
+    let valid = |w: f64| r0 + w * dr >= 0.0;
+
+    if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            return None;
+        }
+        let w = c / (2.0 * b);
+        return valid(w).then_some(w);
+    }
+
+    let disc = b * b - a * c;
+    if disc < 0.0 {
+        return None;
+    }
+    let sqrt = disc.sqrt();
+    let (w0, w1) = ((b + sqrt) / a, (b - sqrt) / a);
+    let (hi, lo) = if w0 >= w1 { (w0, w1) } else { (w1, w0) };
+    if valid(hi) {
+        Some(hi)
+    } else if valid(lo) {
+        Some(lo)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,245 @@
+use crate::graphics2d::path2d::Path2D;
+use xross_core::{XrossClass, xross_methods};
+
+/// Number of f32 values describing one label in [`LabelLayout::add_labels`]:
+/// `anchor_x, anchor_y, width, height, priority`.
+pub const LABEL_INPUT_STRIDE: usize = 5;
+/// Number of f32 values written per label by [`LabelLayout::solve`]:
+/// `x, y, offset_x, offset_y, flags`. `x, y` is the top-left corner of the label.
+pub const LABEL_OUTPUT_STRIDE: usize = 5;
+
+pub const LABEL_PLACED: u32 = 1;
+/// The label was moved away from its preferred spot and should get a leader line.
+pub const LABEL_DISPLACED: u32 = 1 << 1;
+/// No free spot was found within the search radius.
+pub const LABEL_HIDDEN: u32 = 1 << 2;
+
+/// Candidate directions tried on every search ring, preferred ones first.
+const CANDIDATE_DIRS: [(f32, f32); 8] = [
+    (0.0, -1.0),
+    (0.0, 1.0),
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (1.0, -1.0),
+    (-1.0, -1.0),
+    (1.0, 1.0),
+    (-1.0, 1.0),
+];
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Label {
+    pub anchor_x: f32,
+    pub anchor_y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub priority: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Placement {
+    pub x: f32,
+    pub y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub flags: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl Rect {
+    fn overlaps(&self, other: &Rect) -> bool {
+        self.x0 < other.x1 && other.x0 < self.x1 && self.y0 < other.y1 && other.y0 < self.y1
+    }
+}
+
+/// Greedy overlap-free placement of screen-space labels.
+/// Labels are placed in priority order; a label that collides with an already placed
+/// one is pushed outward along [`CANDIDATE_DIRS`] in rings until it fits.
+#[derive(XrossClass)]
+pub struct LabelLayout {
+    labels: Vec<Label>,
+    placements: Vec<Placement>,
+    viewport_width: f32,
+    viewport_height: f32,
+    padding: f32,
+    step: f32,
+    max_displacement: f32,
+    buffer: Vec<f32>,
+}
+
+impl Default for LabelLayout {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            placements: Vec::new(),
+            viewport_width: f32::INFINITY,
+            viewport_height: f32::INFINITY,
+            padding: 2.0,
+            step: 4.0,
+            max_displacement: 120.0,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl LabelLayout {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn clear(&mut self) {
+        self.labels.clear();
+        self.placements.clear();
+        self.buffer.clear();
+    }
+
+    #[xross_method(critical)]
+    pub fn set_viewport(&mut self, width: f32, height: f32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+    }
+
+    #[xross_method(critical)]
+    pub fn set_spacing(&mut self, padding: f32, step: f32, max_displacement: f32) {
+        self.padding = padding.max(0.0);
+        self.step = step.max(0.5);
+        self.max_displacement = max_displacement.max(0.0);
+    }
+
+    #[xross_method]
+    pub fn add_labels(&mut self, data: &[f32]) {
+        for l in data.chunks_exact(LABEL_INPUT_STRIDE) {
+            self.labels.push(Label {
+                anchor_x: l[0],
+                anchor_y: l[1],
+                width: l[2],
+                height: l[3],
+                priority: l[4],
+            });
+        }
+    }
+
+    #[xross_method(panicable)]
+    pub fn solve(&mut self) {
+        let mut order: Vec<usize> = (0..self.labels.len()).collect();
+        order.sort_by(|&a, &b| self.labels[b].priority.total_cmp(&self.labels[a].priority));
+
+        self.placements = vec![Placement::default(); self.labels.len()];
+        let mut placed: Vec<Rect> = Vec::with_capacity(self.labels.len());
+
+        for i in order {
+            let label = self.labels[i];
+            let base_x = label.anchor_x - label.width / 2.0;
+            let base_y = label.anchor_y - label.height;
+
+            let found = self.candidates().find_map(|(dx, dy)| {
+                let rect = self.padded_rect(base_x + dx, base_y + dy, &label);
+                let free = self.in_viewport(&rect) && !placed.iter().any(|r| r.overlaps(&rect));
+                free.then_some((dx, dy, rect))
+            });
+
+            self.placements[i] = match found {
+                Some((dx, dy, rect)) => {
+                    placed.push(rect);
+                    let displaced = dx != 0.0 || dy != 0.0;
+                    Placement {
+                        x: base_x + dx,
+                        y: base_y + dy,
+                        offset_x: dx,
+                        offset_y: dy,
+                        flags: LABEL_PLACED | if displaced { LABEL_DISPLACED } else { 0 },
+                    }
+                }
+                None => Placement {
+                    x: base_x,
+                    y: base_y,
+                    flags: LABEL_HIDDEN,
+                    ..Default::default()
+                },
+            };
+        }
+
+        self.buffer.clear();
+        for p in &self.placements {
+            self.buffer.extend_from_slice(&[
+                p.x,
+                p.y,
+                p.offset_x,
+                p.offset_y,
+                f32::from_bits(p.flags),
+            ]);
+        }
+    }
+
+    /// Appends a leader line from each displaced label's anchor to the closest point
+    /// of its placed rectangle, using the current pen of `path`.
+    #[xross_method]
+    pub fn draw_leaders(&self, path: &mut Path2D) {
+        for (label, p) in self.labels.iter().zip(&self.placements) {
+            if p.flags & LABEL_DISPLACED == 0 {
+                continue;
+            }
+            let tx = label.anchor_x.clamp(p.x, p.x + label.width);
+            let ty = label.anchor_y.clamp(p.y, p.y + label.height);
+            path.move_to(label.anchor_x as f64, label.anchor_y as f64);
+            path.line_to(tx as f64, ty as f64);
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_ptr(&self) -> *const f32 {
+        self.buffer.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_size(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl LabelLayout {
+    pub fn push(&mut self, label: Label) {
+        self.labels.push(label);
+    }
+
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    fn candidates(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let rings = (self.max_displacement / self.step).floor() as i32;
+        std::iter::once((0.0, 0.0)).chain((1..=rings).flat_map(move |ring| {
+            let dist = ring as f32 * self.step;
+            CANDIDATE_DIRS
+                .iter()
+                .map(move |&(dx, dy)| (dx * dist, dy * dist))
+        }))
+    }
+
+    fn padded_rect(&self, x: f32, y: f32, label: &Label) -> Rect {
+        let pad = self.padding / 2.0;
+        Rect {
+            x0: x - pad,
+            y0: y - pad,
+            x1: x + label.width + pad,
+            y1: y + label.height + pad,
+        }
+    }
+
+    fn in_viewport(&self, rect: &Rect) -> bool {
+        let pad = self.padding / 2.0;
+        rect.x0 + pad >= 0.0
+            && rect.y0 + pad >= 0.0
+            && rect.x1 - pad <= self.viewport_width
+            && rect.y1 - pad <= self.viewport_height
+    }
+}
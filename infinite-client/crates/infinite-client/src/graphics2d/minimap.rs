@@ -0,0 +1,246 @@
+use crate::graphics3d::mesh::{BlockPos, SectionPos};
+use crate::infinite::property::BlockAndColor;
+use crate::utils::color::Color;
+use crate::world::snapshot::{WorldSnapshot, WorldView};
+use rustc_hash::FxHashMap;
+use xross_core::{XrossClass, xross_methods};
+
+/// Brightness multipliers used by vanilla maps for terrain sloping down, flat and up.
+const SHADE_LOW: u32 = 180;
+const SHADE_NORMAL: u32 = 220;
+const SHADE_HIGH: u32 = 255;
+
+const DEFAULT_FALLBACK_COLOR: u32 = 0xFF7F7F7F;
+/// How far below the surface (or cave floor) tracked ores are searched for.
+const DEFAULT_HIGHLIGHT_DEPTH: i32 = 16;
+
+#[derive(Clone, Copy)]
+struct ColumnSample {
+    height: i32,
+    color: Option<Color>,
+    highlight: Option<Color>,
+}
+
+/// Top-down RGBA raster of the [`WorldSnapshot`] around the player.
+/// North is up, one pixel per block.
+#[derive(XrossClass)]
+pub struct MinimapRenderer {
+    map_colors: FxHashMap<i32, Color>,
+    highlights: FxHashMap<i32, Color>,
+    fallback_color: Color,
+    min_y: i32,
+    max_y: i32,
+    cave_layer: Option<i32>,
+    highlight_depth: i32,
+    size: usize,
+    pixels: Vec<u8>,
+}
+
+impl Default for MinimapRenderer {
+    fn default() -> Self {
+        Self {
+            map_colors: FxHashMap::default(),
+            highlights: FxHashMap::default(),
+            fallback_color: DEFAULT_FALLBACK_COLOR.into(),
+            min_y: -64,
+            max_y: 319,
+            cave_layer: None,
+            highlight_depth: DEFAULT_HIGHLIGHT_DEPTH,
+            size: 0,
+            pixels: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl MinimapRenderer {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parallel arrays of block state ids and their ARGB map colors.
+    #[xross_method]
+    pub fn set_color_table(&mut self, ids: &[i32], colors: &[i32]) {
+        self.map_colors = ids
+            .iter()
+            .zip(colors)
+            .map(|(&id, &color)| (id, Color::from_raw(color)))
+            .collect();
+    }
+
+    /// Color for non-air ids missing from the color table.
+    #[xross_method(critical)]
+    pub fn set_fallback_color(&mut self, color: i32) {
+        self.fallback_color = Color::from_raw(color);
+    }
+
+    /// Tracked ores, packed like `BlockHighlightFeature::update_highlight_list`.
+    #[xross_method]
+    pub fn set_highlights(&mut self, buff: &[u64]) {
+        self.highlights = buff
+            .iter()
+            .map(|&b| {
+                let item = BlockAndColor::from(b);
+                (item.id as i32, item.color)
+            })
+            .collect();
+    }
+
+    /// Blocks below the surface (or cave floor) searched for tracked ores. Bounds the
+    /// per-column scan when no tracked ore is present.
+    #[xross_method(critical)]
+    pub fn set_highlight_depth(&mut self, depth: i32) {
+        self.highlight_depth = depth.max(0);
+    }
+
+    /// Inclusive world height range.
+    #[xross_method(critical)]
+    pub fn set_height(&mut self, min_y: i32, max_y: i32) {
+        self.min_y = min_y;
+        self.max_y = max_y.max(min_y);
+    }
+
+    /// Renders the floor of the first air gap at or below `y` instead of the surface,
+    /// so the map shows caves rather than the rock the player is standing in.
+    #[xross_method(critical)]
+    pub fn set_cave_layer(&mut self, y: i32) {
+        self.cave_layer = Some(y);
+    }
+
+    #[xross_method(critical)]
+    pub fn disable_cave_layer(&mut self) {
+        self.cave_layer = None;
+    }
+
+    /// Renders a `(2 * radius + 1)` square image centered on the player.
+    #[xross_method(panicable)]
+    pub fn render(&mut self, player_x: i32, player_z: i32, radius: i32) {
+        let radius = radius.max(0);
+        let size = (radius * 2 + 1) as usize;
+        let view = WorldSnapshot::instance().view();
+
+        // One extra row on the north side so the first row can be shaded too.
+        let x0 = player_x - radius;
+        let z0 = player_z - radius - 1;
+        let mut prev_row: Vec<Option<ColumnSample>> = (0..size)
+            .map(|i| self.sample(&view, x0 + i as i32, z0))
+            .collect();
+
+        self.size = size;
+        self.pixels.clear();
+        self.pixels.resize(size * size * 4, 0);
+
+        for row in 0..size {
+            let z = z0 + 1 + row as i32;
+            for (col, north) in prev_row.iter_mut().enumerate() {
+                let sample = self.sample(&view, x0 + col as i32, z);
+                if let Some(s) = sample {
+                    let terrain = s.color.map(|c| shade(c, s.height, north.map(|n| n.height)));
+                    let color = match (s.highlight, terrain) {
+                        (Some(h), Some(t)) => blend(t, h),
+                        (Some(c), None) | (None, Some(c)) => c,
+                        (None, None) => Color::default(),
+                    };
+                    let out = (row * size + col) * 4;
+                    self.pixels[out..out + 4]
+                        .copy_from_slice(&[color.r, color.g, color.b, color.a]);
+                }
+                *north = sample;
+            }
+        }
+    }
+
+    /// Width and height of the last rendered image in pixels.
+    #[xross_method(critical)]
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// RGBA bytes, row-major from the north-west corner.
+    #[xross_method(critical)]
+    pub fn get_pixels_ptr(&self) -> *const u8 {
+        self.pixels.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_pixels_size(&self) -> usize {
+        self.pixels.len()
+    }
+}
+
+impl MinimapRenderer {
+    /// Finds the top visible block of a column. `None` if the column is not loaded.
+    fn sample(&self, view: &WorldView, x: i32, z: i32) -> Option<ColumnSample> {
+        let top = self.cave_layer.unwrap_or(self.max_y).min(self.max_y);
+        let mut loaded = false;
+        let mut found: Option<(i32, Color)> = None;
+        let mut highlight: Option<Color> = None;
+        // In cave mode the floor is only searched for once an air block was passed.
+        let mut in_gap = self.cave_layer.is_none();
+        // Keep scanning below the surface only while looking for tracked ores, and at
+        // most `highlight_depth` blocks deep.
+        let done = |found: Option<(i32, Color)>, highlight: Option<Color>, y: i32| {
+            found.is_some_and(|(floor, _)| {
+                highlight.is_some()
+                    || self.highlights.is_empty()
+                    || y <= floor - self.highlight_depth
+            })
+        };
+
+        let mut section_y = top >> 4;
+        while section_y >= self.min_y >> 4 {
+            let sp = SectionPos::new(x >> 4, section_y, z >> 4);
+            if let Some(section) = view.section(sp) {
+                loaded = true;
+                let base = section_y << 4;
+                let upper = (base + 15).min(top);
+                for y in (base.max(self.min_y)..=upper).rev() {
+                    let id = section.state(SectionPos::local_index(BlockPos::new(x, y, z)));
+                    if highlight.is_none() {
+                        highlight = self.highlights.get(&id).copied();
+                    }
+                    let air = view.is_air_id(id);
+                    in_gap |= air;
+                    if found.is_none() && in_gap && !air {
+                        let color = self
+                            .map_colors
+                            .get(&id)
+                            .copied()
+                            .unwrap_or(self.fallback_color);
+                        found = Some((y, color));
+                    }
+                    if done(found, highlight, y) {
+                        break;
+                    }
+                }
+            }
+            if done(found, highlight, section_y << 4) {
+                break;
+            }
+            section_y -= 1;
+        }
+
+        loaded.then(|| ColumnSample {
+            height: found.map_or(self.min_y, |(y, _)| y),
+            color: found.map(|(_, c)| c),
+            highlight,
+        })
+    }
+}
+
+/// Draws `highlight` over `base` using the highlight's alpha, keeping the base alpha.
+fn blend(base: Color, highlight: Color) -> Color {
+    let opaque = Color::new(base.a, highlight.r, highlight.g, highlight.b);
+    base.mix(opaque, highlight.a as f32 / 255.0)
+}
+
+fn shade(color: Color, height: i32, north: Option<i32>) -> Color {
+    let factor = match north {
+        Some(n) if height > n => SHADE_HIGH,
+        Some(n) if height < n => SHADE_LOW,
+        _ => SHADE_NORMAL,
+    };
+    let apply = |c: u8| (c as u32 * factor / 255) as u8;
+    Color::new(color.a, apply(color.r), apply(color.g), apply(color.b))
+}
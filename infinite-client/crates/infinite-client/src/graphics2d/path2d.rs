@@ -1,3 +1,4 @@
+use crate::graphics2d::gradient::Gradient;
 use crate::utils::color::Color;
 use lyon::lyon_tessellation::{FillVertex, GeometryBuilderError, VertexId};
 use lyon::math::point;
@@ -6,12 +7,60 @@ use lyon::tessellation::{FillGeometryBuilder, FillOptions, FillTessellator, Geom
 use std::f64::consts::PI;
 use xross_core::{XrossClass, xross_methods};
 
-#[derive(XrossClass, Default, Clone)]
+mod boolean;
+mod clip;
+mod dash;
+mod flatten;
+mod hit_test;
+mod round_rect;
+mod svg;
+mod svg_export;
+mod transform;
+
+use clip::ClipRegion;
+use round_rect::{CornerRadii, Radius};
+use transform::Transform2D;
+
+#[derive(XrossClass, Clone)]
 #[xross(clonable)]
 pub struct Path2D {
     segments: Vec<SegmentData>,
     pub pen: Pen,
     buffer: Vec<f32>,
+    tolerance: f64,
+    line_dash: Vec<f64>,
+    fill_gradient: Option<Gradient>,
+    gradient_subdivision: f64,
+    transform: Transform2D,
+    saved_states: Vec<SavedState>,
+    clip_region: Option<ClipRegion>,
+}
+
+/// Drawing state pushed by [`Path2D::save`], mirroring the Canvas drawing state stack.
+#[derive(Clone)]
+struct SavedState {
+    transform: Transform2D,
+    pen: Pen,
+    line_dash: Vec<f64>,
+    fill_gradient: Option<Gradient>,
+    clip_region: Option<ClipRegion>,
+}
+
+impl Default for Path2D {
+    fn default() -> Self {
+        Self {
+            segments: Vec::new(),
+            pen: Pen::default(),
+            buffer: Vec::new(),
+            tolerance: flatten::DEFAULT_TOLERANCE,
+            line_dash: Vec::new(),
+            fill_gradient: None,
+            gradient_subdivision: 0.0,
+            transform: Transform2D::IDENTITY,
+            saved_states: Vec::new(),
+            clip_region: None,
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -107,6 +156,8 @@ pub struct Pen {
     pub line_join: XrossLineJoin,
     #[xross_field]
     pub is_gradient_enabled: bool,
+    #[xross_field]
+    pub line_dash_offset: f64,
 }
 
 #[derive(XrossClass)]
@@ -115,6 +166,15 @@ pub enum XrossFillRule {
     NonZero,
 }
 
+#[derive(XrossClass, Clone, Copy, Debug, PartialEq)]
+pub enum XrossPathOp {
+    Union,
+    Intersect,
+    /// This path minus the other one.
+    Difference,
+    Xor,
+}
+
 #[xross_methods]
 impl Path2D {
     #[xross_new]
@@ -127,6 +187,14 @@ impl Path2D {
         self.segments.clear();
     }
 
+    /// Maximum distance in pixels between flattened curves and the real curve.
+    #[xross_method(critical)]
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        if tolerance > 0.0 {
+            self.tolerance = tolerance;
+        }
+    }
+
     #[xross_method(critical)]
     pub fn set_pen(
         &mut self,
@@ -143,6 +211,102 @@ impl Path2D {
         self.pen.is_gradient_enabled = enable_gradient;
     }
 
+    /// Alternating dash and gap lengths used by [`Self::tessellate_stroke`], like Canvas
+    /// `setLineDash`. An empty pattern draws solid lines; patterns containing negative
+    /// or non-finite values are ignored. Patterns that would split the path into an
+    /// excessive number of dashes also stroke solid lines.
+    #[xross_method]
+    pub fn set_line_dash(&mut self, pattern: &[f64]) {
+        if let Some(pattern) = dash::normalize_pattern(pattern) {
+            self.line_dash = pattern;
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn set_line_dash_offset(&mut self, offset: f64) {
+        if offset.is_finite() {
+            self.pen.line_dash_offset = offset;
+        }
+    }
+
+    /// Uses `gradient` instead of the pen color for [`Self::tessellate_fill`].
+    /// The gradient is copied, later changes to it need another call.
+    #[xross_method]
+    pub fn set_fill_gradient(&mut self, gradient: &Gradient) {
+        self.fill_gradient = Some(gradient.clone());
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_fill_gradient(&mut self) {
+        self.fill_gradient = None;
+    }
+
+    /// Splits gradient-filled triangles until no edge is longer than `max_edge`, so
+    /// multi-stop and non-linear gradients survive per-vertex interpolation.
+    /// `0` disables subdivision.
+    #[xross_method(critical)]
+    pub fn set_gradient_subdivision(&mut self, max_edge: f64) {
+        self.gradient_subdivision = max_edge.max(0.0);
+    }
+
+    #[xross_method(critical)]
+    pub fn save(&mut self) {
+        self.saved_states.push(SavedState {
+            transform: self.transform,
+            pen: self.pen,
+            line_dash: self.line_dash.clone(),
+            fill_gradient: self.fill_gradient.clone(),
+            clip_region: self.clip_region.clone(),
+        });
+    }
+
+    /// Restores the state of the matching [`Self::save`]. Does nothing when the stack is empty.
+    #[xross_method(critical)]
+    pub fn restore(&mut self) {
+        if let Some(state) = self.saved_states.pop() {
+            self.transform = state.transform;
+            self.pen = state.pen;
+            self.line_dash = state.line_dash;
+            self.fill_gradient = state.fill_gradient;
+            self.clip_region = state.clip_region;
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.apply_transform(Transform2D::translation(x, y));
+    }
+
+    /// `angle` is in radians, clockwise on screen since y points down.
+    #[xross_method(critical)]
+    pub fn rotate(&mut self, angle: f64) {
+        self.apply_transform(Transform2D::rotation(angle));
+    }
+
+    #[xross_method(critical)]
+    pub fn scale(&mut self, x: f64, y: f64) {
+        self.apply_transform(Transform2D::scaling(x, y));
+    }
+
+    /// Multiplies the current matrix by `[a c e; b d f; 0 0 1]`.
+    #[xross_method(critical)]
+    pub fn transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        self.apply_transform(Transform2D::new(a, b, c, d, e, f));
+    }
+
+    #[xross_method(critical)]
+    pub fn set_transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        let matrix = Transform2D::new(a, b, c, d, e, f);
+        if matrix.is_finite() {
+            self.transform = matrix;
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn reset_transform(&mut self) {
+        self.transform = Transform2D::IDENTITY;
+    }
+
     #[xross_method]
     pub fn move_to(&mut self, x: f64, y: f64) {
         self.segments.push(SegmentData::default());
@@ -151,14 +315,21 @@ impl Path2D {
         current_segment.points.push(point);
     }
 
+    /// Builds a point from user-space coordinates, applying the current transform.
     fn point(&self, x: f64, y: f64) -> PointData {
+        let (x, y) = self.transform.apply(x, y);
+        self.device_point(x, y)
+    }
+
+    /// Builds a point from coordinates that are already transformed.
+    fn device_point(&self, x: f64, y: f64) -> PointData {
         PointData {
             x,
             y,
             color: self.pen.color,
             line_cap: self.pen.line_cap,
             line_join: self.pen.line_join,
-            width: self.pen.width,
+            width: self.pen.width * self.transform.width_scale(),
         }
     }
 
@@ -167,14 +338,14 @@ impl Path2D {
         let needs_new_segment = self.segments.last().map(|s| s.is_closed).unwrap_or(false);
 
         if needs_new_segment {
-            let last_start_point = self
-                .segments
-                .last()
-                .and_then(|s| s.points.first())
-                .map(|p| (p.x, p.y));
-
-            if let Some((sx, sy)) = last_start_point {
-                self.move_to(sx, sy);
+            // The start point is already transformed, so reuse it as is.
+            let last_start_point = self.segments.last().and_then(|s| s.points.first()).copied();
+
+            if let Some(start) = last_start_point {
+                self.segments.push(SegmentData {
+                    points: vec![start],
+                    is_closed: false,
+                });
             } else {
                 self.move_to(x, y);
             }
@@ -218,21 +389,25 @@ impl Path2D {
         let mut p = self.point(x, y);
         if self.pen.is_gradient_enabled {
             p.color = start_color.mix(self.pen.color, t);
-            p.width = start_width + (self.pen.width - start_width) * (t as f64);
-        } else {
-            p.width = self.pen.width;
+            p.width = start_width + (p.width - start_width) * (t as f64);
         }
         self.push_point(p);
     }
 
     #[xross_method]
     pub fn bezier_curve_to(&mut self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        let start_pos = self.last_local_position().unwrap_or((0.0, 0.0));
         let last = self.last_point();
-        let start_pos = last.map(|p| (p.x, p.y)).unwrap_or((0.0, 0.0));
         let start_color = last.map(|p| p.color).unwrap_or(self.pen.color);
         let start_width = last.map(|p| p.width).unwrap_or(self.pen.width);
 
-        let steps = 20;
+        let steps = flatten::cubic_steps(
+            start_pos,
+            (cp1x, cp1y),
+            (cp2x, cp2y),
+            (x, y),
+            self.flatten_tolerance(),
+        );
         for i in 1..=steps {
             let t = i as f64 / steps as f64;
             let inv_t = 1.0 - t;
@@ -252,12 +427,12 @@ impl Path2D {
 
     #[xross_method]
     pub fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        let start = self.last_local_position().unwrap_or((0.0, 0.0));
         let last = self.last_point();
-        let start = last.map(|p| (p.x, p.y)).unwrap_or((0.0, 0.0));
         let start_color = last.map(|p| p.color).unwrap_or(self.pen.color);
         let start_width = last.map(|p| p.width).unwrap_or(self.pen.width);
 
-        let steps = 15;
+        let steps = flatten::quadratic_steps(start, (cpx, cpy), (x, y), self.flatten_tolerance());
         for i in 1..=steps {
             let t = i as f64 / steps as f64;
             let inv_t = 1.0 - t;
@@ -319,7 +494,7 @@ impl Path2D {
 
         let last = self.last_point();
         let start_width = last.map(|p| p.width).unwrap_or(self.pen.width);
-        let steps = (diff.abs() / (PI / 18.0)).ceil().max(10.0) as i32;
+        let steps = flatten::arc_steps(radius, diff, self.flatten_tolerance()) as i32;
         let start_color = last.map(|p| p.color).unwrap_or(self.pen.color);
 
         for i in 0..=steps {
@@ -353,7 +528,7 @@ impl Path2D {
 
     #[xross_method]
     pub fn arc_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
-        let start = self.last_point().map(|p| (p.x, p.y)).unwrap_or((x1, y1));
+        let start = self.last_local_position().unwrap_or((x1, y1));
 
         let v1 = (start.0 - x1, start.1 - y1);
         let v2 = (x2 - x1, y2 - y1);
@@ -447,7 +622,8 @@ impl Path2D {
             };
         }
 
-        let steps = 40;
+        let steps =
+            flatten::arc_steps(radius_x.max(radius_y), diff, self.flatten_tolerance()) as i32;
         let start_color = self.last_point().map(|p| p.color).unwrap_or(self.pen.color);
         let cos_rot = rotation.cos();
         let sin_rot = rotation.sin();
@@ -468,10 +644,228 @@ impl Path2D {
         }
     }
 
+    /// Adds the rectangle as a closed subpath.
+    #[xross_method]
+    pub fn rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        if ![x, y, w, h].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        self.move_to(x, y);
+        self.line_to(x + w, y);
+        self.line_to(x + w, y + h);
+        self.line_to(x, y + h);
+        self.close_path();
+    }
+
+    /// Canvas `roundRect` with circular corners. `radii` holds 1 to 4 values, expanded
+    /// like CSS `border-radius` (upper-left, upper-right, lower-right, lower-left).
+    #[xross_method]
+    pub fn round_rect(&mut self, x: f64, y: f64, w: f64, h: f64, radii: &[f64]) {
+        let pairs: Vec<f64> = radii.iter().flat_map(|&r| [r, r]).collect();
+        self.round_rect_elliptical(x, y, w, h, &pairs);
+    }
+
+    /// Like [`Self::round_rect`], but `radii` holds 1 to 4 `(x, y)` pairs.
+    /// Invalid radii add nothing; radii larger than the rectangle are scaled down together.
+    #[xross_method]
+    pub fn round_rect_elliptical(&mut self, x: f64, y: f64, w: f64, h: f64, radii: &[f64]) {
+        if ![x, y, w, h].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        let Some(corners) = CornerRadii::from_pairs(radii, w, h) else {
+            return;
+        };
+        // Negative sizes mirror the shape, radii offsets follow the same direction.
+        let sx = if w < 0.0 { -1.0 } else { 1.0 };
+        let sy = if h < 0.0 { -1.0 } else { 1.0 };
+        let (ul, ur, lr, ll) = (
+            corners.upper_left,
+            corners.upper_right,
+            corners.lower_right,
+            corners.lower_left,
+        );
+
+        self.move_to(x + ul.x * sx, y);
+        self.line_to(x + w - ur.x * sx, y);
+        self.round_rect_corner(x + w - ur.x * sx, y + ur.y * sy, ur, -PI / 2.0, sx, sy);
+        self.line_to(x + w, y + h - lr.y * sy);
+        self.round_rect_corner(x + w - lr.x * sx, y + h - lr.y * sy, lr, 0.0, sx, sy);
+        self.line_to(x + ll.x * sx, y + h);
+        self.round_rect_corner(x + ll.x * sx, y + h - ll.y * sy, ll, PI / 2.0, sx, sy);
+        self.line_to(x, y + ul.y * sy);
+        self.round_rect_corner(x + ul.x * sx, y + ul.y * sy, ul, PI, sx, sy);
+        self.close_path();
+    }
+
+    /// Appends SVG path data (the `d` attribute) in the current user space.
+    /// Returns `false` if a syntax error stopped parsing; the part before it is kept.
+    #[xross_method]
+    pub fn append_svg_path(&mut self, data: &str) -> bool {
+        svg::append_path_data(self, data)
+    }
+
+    /// Draws the `<path>`, `<rect>` and `<circle>` elements of an SVG document with their
+    /// fill and stroke colors, replacing the buffer with the combined output.
+    /// The current transform and clip apply; the path and buffer are reset.
+    /// The buffer mixes `3.0` fill triangles and `4.0` stroke quads, so it must be read
+    /// by a consumer handling both, like the one behind Kotlin `fillPath`; `strokePath`
+    /// only draws quads and would drop every fill.
+    /// Returns the number of elements that produced output.
+    #[xross_method]
+    pub fn load_svg(&mut self, document: &str) -> usize {
+        let elements = svg::parse_document(document);
+        let mut output = Vec::new();
+        let mut drawn = 0;
+
+        self.save();
+        self.fill_gradient = None;
+        self.line_dash.clear();
+        self.pen.is_gradient_enabled = false;
+        self.pen.line_cap = XrossLineCap::Butt;
+        self.pen.line_join = XrossLineJoin::Miter;
+
+        for element in &elements {
+            self.begin();
+            self.pen.width = element.stroke_width;
+            element.append_to(self);
+            let before = output.len();
+
+            if let Some(fill) = element.fill {
+                self.pen.color = fill;
+                self.tessellate_fill(if element.even_odd {
+                    XrossFillRule::EvenOdd
+                } else {
+                    XrossFillRule::NonZero
+                });
+                output.append(&mut self.buffer);
+            }
+            if let Some(stroke) = element.stroke {
+                self.pen.color = stroke;
+                self.tessellate_stroke();
+                output.append(&mut self.buffer);
+            }
+            if output.len() > before {
+                drawn += 1;
+            }
+        }
+
+        self.restore();
+        self.begin();
+        self.buffer = output;
+        drawn
+    }
+
     #[xross_method]
     pub fn tessellate_fill(&mut self, rule: XrossFillRule) {
-        self.buffer.clear();
+        let mut buffer = Vec::new();
+        self.fill_into(&mut buffer, rule, self.fill_gradient.as_ref());
+        self.buffer = self.apply_clip(buffer);
+    }
+
+    /// Replaces this path with the result of `op` between this path and `other`, both
+    /// read with `rule`. The result is flattened into closed subpaths drawn with the
+    /// current pen, with holes wound so that either fill rule fills them correctly.
+    #[xross_method]
+    pub fn combine(&mut self, other: &Path2D, op: XrossPathOp, rule: XrossFillRule) {
+        let even_odd = matches!(rule, XrossFillRule::EvenOdd);
+        let a = self.contours();
+        let b = other.contours();
+        let result = boolean::combine(
+            &boolean::Shape {
+                contours: &a,
+                even_odd,
+            },
+            &boolean::Shape {
+                contours: &b,
+                even_odd,
+            },
+            op,
+        );
+
+        self.segments = result
+            .into_iter()
+            .map(|contour| SegmentData {
+                points: contour
+                    .into_iter()
+                    .map(|(x, y)| self.device_point(x, y))
+                    .collect(),
+                is_closed: true,
+            })
+            .collect();
+    }
+
+    /// Intersects the clip region with the current path. Later fill and stroke output is
+    /// limited to that area until the matching [`Self::restore`].
+    #[xross_method]
+    pub fn clip(&mut self, rule: XrossFillRule) {
+        let mut triangles = Vec::new();
+        self.fill_into(&mut triangles, rule, None);
+        let region = ClipRegion::from_buffer(&triangles);
+        self.clip_region = Some(match self.clip_region.take() {
+            Some(current) => current.intersect(&region),
+            None => region,
+        });
+    }
+
+    #[xross_method]
+    pub fn tessellate_stroke(&mut self) {
+        let mut output_buffer = Vec::new();
+        self.stroke_into(&mut output_buffer);
+        self.buffer = self.apply_clip(output_buffer);
+    }
+
+    /// Canvas `isPointInPath`. `x, y` are in the same space as the stored points, i.e.
+    /// not affected by the current transform.
+    #[xross_method]
+    pub fn is_point_in_path(&self, x: f64, y: f64, rule: XrossFillRule) -> bool {
+        let even_odd = matches!(rule, XrossFillRule::EvenOdd);
+        hit_test::contours_contain(&self.contours(), x, y, even_odd)
+    }
+
+    /// Canvas `isPointInStroke`, tested against the geometry [`Self::tessellate_stroke`]
+    /// produces for the current pen and dash pattern, ignoring the clip region.
+    #[xross_method]
+    pub fn is_point_in_stroke(&self, x: f64, y: f64) -> bool {
+        let mut buffer = Vec::new();
+        self.stroke_into(&mut buffer);
+        hit_test::buffer_contains(&buffer, x, y)
+    }
+
+    /// Dumps the current segments and/or the last tessellation buffer as an SVG document.
+    /// Buffer primitives become polygons filled with the average of their vertex colors,
+    /// segments are drawn on top as thin magenta outlines.
+    #[xross_method]
+    pub fn export_svg(&self, include_segments: bool, include_buffer: bool) -> String {
+        svg_export::export(
+            include_segments.then_some(self.segments.as_slice()),
+            include_buffer.then_some(self.buffer.as_slice()),
+        )
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_ptr(&self) -> *const f32 {
+        self.buffer.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_size(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl Path2D {
+    /// Tolerance in user-space units, so curves stay within the pixel tolerance after
+    /// the current transform.
+    fn flatten_tolerance(&self) -> f64 {
+        let scale = self.transform.max_scale();
+        if scale > 1e-9 {
+            self.tolerance / scale
+        } else {
+            self.tolerance
+        }
+    }
 
+    fn fill_into(&self, buffer: &mut Vec<f32>, rule: XrossFillRule, gradient: Option<&Gradient>) {
         let mut builder = Path::builder();
         for segment in &self.segments {
             if segment.points.is_empty() {
@@ -493,9 +887,13 @@ impl Path2D {
         let mut tessellator = FillTessellator::new();
 
         let mut output = FillOutput {
-            buffer: &mut self.buffer,
+            buffer,
             vertices: Vec::new(),
             current_pen_color: self.pen.color.into_raw(),
+            gradient,
+            max_edge: self.gradient_subdivision as f32,
+            // Gradients are defined in the user space that is current when filling.
+            gradient_space: self.transform.inverse().unwrap_or_default(),
         };
         let options = FillOptions::default().with_fill_rule(match rule {
             XrossFillRule::EvenOdd => FillRule::EvenOdd,
@@ -505,111 +903,201 @@ impl Path2D {
         let _ = tessellator.tessellate_path(&path, &options, &mut output);
     }
 
-    #[xross_method]
-    pub fn tessellate_stroke(&mut self) {
-        let mut output_buffer = Vec::new();
+    /// Subpath outlines as used for filling, every subpath implicitly closed.
+    fn contours(&self) -> Vec<Vec<(f64, f64)>> {
+        self.segments
+            .iter()
+            .map(|s| s.points.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    fn stroke_into(&self, output_buffer: &mut Vec<f32>) {
         let cap = self.pen.line_cap.into();
         let join = self.pen.line_join.into();
         let enable_gradient = self.pen.is_gradient_enabled;
+        let pen_color = self.pen.color.into_raw();
+
+        // Patterns that would produce too many dashes fall back to a solid stroke.
+        let dashed = dash::is_dashed(&self.line_dash)
+            .then(|| {
+                dash::dash_segments(&self.segments, &self.line_dash, self.pen.line_dash_offset)
+            })
+            .flatten();
+        let segments = dashed.as_ref().unwrap_or(&self.segments);
+
+        for segment in segments {
+            Self::static_stroke_segment(
+                output_buffer,
+                segment,
+                pen_color,
+                cap,
+                join,
+                enable_gradient,
+                dashed.is_some(),
+            );
+        }
+    }
 
-        for segment in &self.segments {
-            let n = segment.points.len();
-            if n < 2 {
-                continue;
-            }
+    fn apply_clip(&self, buffer: Vec<f32>) -> Vec<f32> {
+        match &self.clip_region {
+            Some(region) => region.clip_buffer(&buffer),
+            None => buffer,
+        }
+    }
 
-            let is_closed = segment.is_closed;
+    /// Quarter ellipse starting at `start_angle` (for a positive-size rectangle),
+    /// mirrored by the signs of the rectangle size.
+    fn round_rect_corner(
+        &mut self,
+        cx: f64,
+        cy: f64,
+        radius: Radius,
+        start_angle: f64,
+        sx: f64,
+        sy: f64,
+    ) {
+        if radius.x <= 0.0 || radius.y <= 0.0 {
+            return;
+        }
+        let mirror = |angle: f64| {
+            let angle = if sx < 0.0 { PI - angle } else { angle };
+            if sy < 0.0 { -angle } else { angle }
+        };
+        self.ellipse(
+            cx,
+            cy,
+            radius.x,
+            radius.y,
+            0.0,
+            mirror(start_angle),
+            mirror(start_angle + PI / 2.0),
+            sx * sy < 0.0,
+        );
+    }
 
-            let mut dirs = Vec::with_capacity(n);
-            for i in 0..n {
-                let p0 = &segment.points[i];
-                let p1 = &segment.points[(i + 1) % n];
-                dirs.push(normalize(p1.x - p0.x, p1.y - p0.y));
-            }
+    fn apply_transform(&mut self, matrix: Transform2D) {
+        // Canvas ignores transforms with non-finite arguments.
+        let result = self.transform.then(&matrix);
+        if result.is_finite() {
+            self.transform = result;
+        }
+    }
 
-            let loop_limit = if is_closed { n } else { n - 1 };
-            for i in 0..loop_limit {
-                let i_curr = i % n;
-                let i_next = (i + 1) % n;
+    /// The last point mapped back into the current user space.
+    fn last_local_position(&self) -> Option<(f64, f64)> {
+        let last = self.last_point()?;
+        let inverse = self.transform.inverse()?;
+        Some(inverse.apply(last.x, last.y))
+    }
 
-                let p0 = &segment.points[i_curr];
-                let p1 = &segment.points[i_next];
-                let v_curr = dirs[i_curr];
+    fn static_stroke_segment(
+        buffer: &mut Vec<f32>,
+        segment: &SegmentData,
+        pen_color: i32,
+        cap: LineCap,
+        join: LineJoin,
+        enable_gradient: bool,
+        is_dash: bool,
+    ) {
+        let n = segment.points.len();
+        if n < 2 {
+            return;
+        }
 
-                if v_curr == (0.0, 0.0) {
-                    continue;
-                }
+        let is_closed = segment.is_closed;
+
+        // Zero-length dashes still get their caps, oriented along the x axis like Canvas
+        // does. Solid strokes keep skipping zero-length subpaths.
+        let first = &segment.points[0];
+        if is_dash
+            && !is_closed
+            && segment
+                .points
+                .iter()
+                .all(|p| p.x == first.x && p.y == first.y)
+        {
+            let half_w = first.width / 2.0;
+            Self::static_push_cap(buffer, first, (1.0, 0.0), (0.0, half_w), cap, true);
+            Self::static_push_cap(buffer, first, (1.0, 0.0), (0.0, half_w), cap, false);
+            return;
+        }
 
-                let half_w0 = p0.width / 2.0;
-                let half_w1 = p1.width / 2.0;
+        let mut dirs = Vec::with_capacity(n);
+        for i in 0..n {
+            let p0 = &segment.points[i];
+            let p1 = &segment.points[(i + 1) % n];
+            dirs.push(normalize(p1.x - p0.x, p1.y - p0.y));
+        }
 
-                let n_curr = (-v_curr.1, v_curr.0);
+        let loop_limit = if is_closed { n } else { n - 1 };
+        for i in 0..loop_limit {
+            let i_curr = i % n;
+            let i_next = (i + 1) % n;
 
-                let colors = if enable_gradient {
-                    [
-                        p0.color.into_raw(),
-                        p0.color.into_raw(),
-                        p1.color.into_raw(),
-                        p1.color.into_raw(),
-                    ]
-                } else {
-                    let c = self.pen.color.into_raw();
-                    [c, c, c, c]
-                };
+            let p0 = &segment.points[i_curr];
+            let p1 = &segment.points[i_next];
+            let v_curr = dirs[i_curr];
 
-                Self::static_push_quad(
-                    &mut output_buffer,
-                    (p0.x + n_curr.0 * half_w0, p0.y + n_curr.1 * half_w0),
-                    (p0.x - n_curr.0 * half_w0, p0.y - n_curr.1 * half_w0),
-                    (p1.x - n_curr.0 * half_w1, p1.y - n_curr.1 * half_w1),
-                    (p1.x + n_curr.0 * half_w1, p1.y + n_curr.1 * half_w1),
-                    colors,
-                );
+            if v_curr == (0.0, 0.0) {
+                continue;
+            }
 
-                if is_closed || i < n - 2 {
-                    let v_next = dirs[i_next];
-                    Self::static_push_join(&mut output_buffer, p1, v_curr, v_next, join);
-                }
+            let half_w0 = p0.width / 2.0;
+            let half_w1 = p1.width / 2.0;
 
-                if !is_closed {
-                    if i == 0 {
-                        Self::static_push_cap(
-                            &mut output_buffer,
-                            p0,
-                            v_curr,
-                            (n_curr.0 * half_w0, n_curr.1 * half_w0),
-                            cap,
-                            true,
-                        );
-                    }
-                    if i == n - 2 {
-                        Self::static_push_cap(
-                            &mut output_buffer,
-                            p1,
-                            v_curr,
-                            (n_curr.0 * half_w1, n_curr.1 * half_w1),
-                            cap,
-                            false,
-                        );
-                    }
+            let n_curr = (-v_curr.1, v_curr.0);
+
+            let colors = if enable_gradient {
+                [
+                    p0.color.into_raw(),
+                    p0.color.into_raw(),
+                    p1.color.into_raw(),
+                    p1.color.into_raw(),
+                ]
+            } else {
+                let c = pen_color;
+                [c, c, c, c]
+            };
+
+            Self::static_push_quad(
+                buffer,
+                (p0.x + n_curr.0 * half_w0, p0.y + n_curr.1 * half_w0),
+                (p0.x - n_curr.0 * half_w0, p0.y - n_curr.1 * half_w0),
+                (p1.x - n_curr.0 * half_w1, p1.y - n_curr.1 * half_w1),
+                (p1.x + n_curr.0 * half_w1, p1.y + n_curr.1 * half_w1),
+                colors,
+            );
+
+            if is_closed || i < n - 2 {
+                let v_next = dirs[i_next];
+                Self::static_push_join(buffer, p1, v_curr, v_next, join);
+            }
+
+            if !is_closed {
+                if i == 0 {
+                    Self::static_push_cap(
+                        buffer,
+                        p0,
+                        v_curr,
+                        (n_curr.0 * half_w0, n_curr.1 * half_w0),
+                        cap,
+                        true,
+                    );
+                }
+                if i == n - 2 {
+                    Self::static_push_cap(
+                        buffer,
+                        p1,
+                        v_curr,
+                        (n_curr.0 * half_w1, n_curr.1 * half_w1),
+                        cap,
+                        false,
+                    );
                 }
             }
         }
-        self.buffer = output_buffer;
-    }
-
-    #[xross_method(critical)]
-    pub fn get_buffer_ptr(&self) -> *const f32 {
-        self.buffer.as_ptr()
-    }
-
-    #[xross_method(critical)]
-    pub fn get_buffer_size(&self) -> usize {
-        self.buffer.len()
     }
-}
 
-impl Path2D {
     fn static_push_quad(
         buffer: &mut Vec<f32>,
         q0: (f64, f64),
@@ -858,6 +1346,9 @@ impl Path2D {
     }
 }
 
+/// Caps the triangle count of a single subdivided triangle at `2^MAX_SUBDIVISION_DEPTH`.
+const MAX_SUBDIVISION_DEPTH: u32 = 10;
+
 struct VertexInfo {
     position: [f32; 2],
     color: i32,
@@ -867,6 +1358,56 @@ struct FillOutput<'a> {
     buffer: &'a mut Vec<f32>,
     vertices: Vec<VertexInfo>,
     current_pen_color: i32,
+    gradient: Option<&'a Gradient>,
+    max_edge: f32,
+    gradient_space: Transform2D,
+}
+
+impl FillOutput<'_> {
+    fn sample(&self, gradient: &Gradient, x: f32, y: f32) -> i32 {
+        let (x, y) = self.gradient_space.apply(x as f64, y as f64);
+        gradient.sample(x, y).into_raw()
+    }
+
+    fn push_triangle(&mut self, positions: [[f32; 2]; 3], colors: [i32; 3]) {
+        self.buffer.push(3.0);
+        for p in &positions {
+            self.buffer.push(p[0]);
+            self.buffer.push(p[1]);
+        }
+        for &c in &colors {
+            self.buffer.push(f32::from_bits(c as u32));
+        }
+    }
+
+    /// Halves the longest edge until every edge fits in `max_edge`, sampling the
+    /// gradient at each new vertex.
+    fn push_subdivided(&mut self, gradient: &Gradient, positions: [[f32; 2]; 3], depth: u32) {
+        let edge_len = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+        let lengths = [
+            edge_len(positions[0], positions[1]),
+            edge_len(positions[1], positions[2]),
+            edge_len(positions[2], positions[0]),
+        ];
+        let (longest, &len) = lengths
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+
+        if len <= self.max_edge || depth >= MAX_SUBDIVISION_DEPTH {
+            let colors = positions.map(|p| self.sample(gradient, p[0], p[1]));
+            self.push_triangle(positions, colors);
+            return;
+        }
+
+        let a = positions[longest];
+        let b = positions[(longest + 1) % 3];
+        let c = positions[(longest + 2) % 3];
+        let mid = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+        self.push_subdivided(gradient, [a, mid, c], depth + 1);
+        self.push_subdivided(gradient, [mid, b, c], depth + 1);
+    }
 }
 
 impl<'a> GeometryBuilder for FillOutput<'a> {
@@ -877,16 +1418,16 @@ impl<'a> GeometryBuilder for FillOutput<'a> {
     fn end_geometry(&mut self) {}
 
     fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
-        self.buffer.push(3.0);
         let ids = [a, b, c];
-        for &id in &ids {
-            let v = &self.vertices[id.0 as usize];
-            self.buffer.push(v.position[0]);
-            self.buffer.push(v.position[1]);
-        }
-        for &id in &ids {
-            let v = &self.vertices[id.0 as usize];
-            self.buffer.push(f32::from_bits(v.color as u32));
+        let positions = ids.map(|id| self.vertices[id.0 as usize].position);
+        match self.gradient {
+            Some(gradient) if self.max_edge > 0.0 => {
+                self.push_subdivided(gradient, positions, 0);
+            }
+            _ => {
+                let colors = ids.map(|id| self.vertices[id.0 as usize].color);
+                self.push_triangle(positions, colors);
+            }
         }
     }
     fn abort_geometry(&mut self) {
@@ -897,7 +1438,10 @@ impl<'a> GeometryBuilder for FillOutput<'a> {
 impl<'a> FillGeometryBuilder for FillOutput<'a> {
     fn add_fill_vertex(&mut self, vertex: FillVertex) -> Result<VertexId, GeometryBuilderError> {
         let pos = vertex.position();
-        let color = self.current_pen_color;
+        let color = match self.gradient {
+            Some(gradient) => self.sample(gradient, pos.x, pos.y),
+            None => self.current_pen_color,
+        };
 
         self.vertices.push(VertexInfo {
             position: [pos.x, pos.y],
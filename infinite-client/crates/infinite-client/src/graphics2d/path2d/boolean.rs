@@ -0,0 +1,202 @@
+//! Boolean operations between flattened paths.
+//!
+//! All edges of both inputs are split at their mutual intersections, then every piece is
+//! kept when the operation result differs on its two sides, oriented with the result on
+//! its left. Chaining the kept edges gives closed contours whose winding number is `1`
+//! inside the result and `0` outside, so holes fill correctly with either fill rule.
+
+use super::{XrossPathOp, hit_test};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Points closer than this are merged into one vertex.
+const MERGE_EPSILON: f64 = 1e-7;
+/// Parameter range at edge ends treated as the end point itself.
+const PARAM_EPSILON: f64 = 1e-9;
+
+type Point = (f64, f64);
+
+fn keeps(op: XrossPathOp, in_a: bool, in_b: bool) -> bool {
+    match op {
+        XrossPathOp::Union => in_a || in_b,
+        XrossPathOp::Intersect => in_a && in_b,
+        XrossPathOp::Difference => in_a && !in_b,
+        XrossPathOp::Xor => in_a != in_b,
+    }
+}
+
+/// A set of implicitly closed polygons interpreted with a fill rule.
+pub struct Shape<'a> {
+    pub contours: &'a [Vec<Point>],
+    pub even_odd: bool,
+}
+
+impl Shape<'_> {
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.contours.iter().filter(|c| c.len() >= 3).flat_map(|c| {
+            (0..c.len())
+                .map(move |i| (c[i], c[(i + 1) % c.len()]))
+                .filter(|(a, b)| a != b)
+        })
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        hit_test::is_inside(hit_test::winding_number(self.edges(), x, y), self.even_odd)
+    }
+}
+
+/// Returns the closed contours of `op(a, b)`.
+pub fn combine(a: &Shape, b: &Shape, op: XrossPathOp) -> Vec<Vec<Point>> {
+    let edges: Vec<(Point, Point)> = a.edges().chain(b.edges()).collect();
+    let mut pool = VertexPool::default();
+    let mut pieces: FxHashSet<(usize, usize)> = FxHashSet::default();
+
+    for (i, &(p0, p1)) in edges.iter().enumerate() {
+        let mut params = vec![0.0, 1.0];
+        for (j, &(q0, q1)) in edges.iter().enumerate() {
+            if i != j {
+                split_params(p0, p1, q0, q1, &mut params);
+            }
+        }
+        params.sort_by(f64::total_cmp);
+
+        let mut prev = pool.insert(p0);
+        for &t in &params[1..] {
+            let point = if t >= 1.0 {
+                p1
+            } else {
+                (p0.0 + (p1.0 - p0.0) * t, p0.1 + (p1.1 - p0.1) * t)
+            };
+            let next = pool.insert(point);
+            if next != prev {
+                // Coincident pieces from both inputs collapse into one undirected edge.
+                pieces.insert((prev.min(next), prev.max(next)));
+            }
+            prev = next;
+        }
+    }
+
+    let mut outgoing: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+    for &(u, v) in &pieces {
+        let (p, q) = (pool.points[u], pool.points[v]);
+        let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+        let len = dx.hypot(dy);
+        let offset = (len * 1e-3).min(1e-4) / len;
+        let mid = ((p.0 + q.0) / 2.0, (p.1 + q.1) / 2.0);
+        let left = (mid.0 - dy * offset, mid.1 + dx * offset);
+        let right = (mid.0 + dy * offset, mid.1 - dx * offset);
+
+        let sample = |pt: Point| keeps(op, a.contains(pt.0, pt.1), b.contains(pt.0, pt.1));
+        match (sample(left), sample(right)) {
+            (true, false) => outgoing.entry(u).or_default().push(v),
+            (false, true) => outgoing.entry(v).or_default().push(u),
+            _ => {}
+        }
+    }
+
+    let mut contours = Vec::new();
+    let mut starts: Vec<usize> = outgoing.keys().copied().collect();
+    starts.sort_unstable();
+    for start in starts {
+        while let Some(first) = outgoing.get_mut(&start).and_then(Vec::pop) {
+            let mut indices = vec![start];
+            let mut current = first;
+            while current != start {
+                indices.push(current);
+                match outgoing.get_mut(&current).and_then(Vec::pop) {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            if current == start {
+                let contour = simplify(indices.iter().map(|&i| pool.points[i]).collect());
+                if contour.len() >= 3 {
+                    contours.push(contour);
+                }
+            }
+        }
+    }
+    contours
+}
+
+/// Adds the parameters along `p0..p1` where `q0..q1` crosses or touches it.
+fn split_params(p0: Point, p1: Point, q0: Point, q1: Point, params: &mut Vec<f64>) {
+    let r = (p1.0 - p0.0, p1.1 - p0.1);
+    let s = (q1.0 - q0.0, q1.1 - q0.1);
+    let qp = (q0.0 - p0.0, q0.1 - p0.1);
+    let denom = r.0 * s.1 - r.1 * s.0;
+    let len_sq = r.0 * r.0 + r.1 * r.1;
+    let inner = PARAM_EPSILON..=1.0 - PARAM_EPSILON;
+
+    if denom.abs() > 1e-12 * len_sq.max(1.0) {
+        let t = (qp.0 * s.1 - qp.1 * s.0) / denom;
+        let u = (qp.0 * r.1 - qp.1 * r.0) / denom;
+        if inner.contains(&t) && (-PARAM_EPSILON..=1.0 + PARAM_EPSILON).contains(&u) {
+            params.push(t);
+        }
+        return;
+    }
+
+    // Parallel: split only when the edges are collinear and overlap.
+    if (qp.0 * r.1 - qp.1 * r.0).abs() > MERGE_EPSILON * len_sq.sqrt() {
+        return;
+    }
+    for q in [q0, q1] {
+        let t = ((q.0 - p0.0) * r.0 + (q.1 - p0.1) * r.1) / len_sq;
+        if inner.contains(&t) {
+            params.push(t);
+        }
+    }
+}
+
+/// Drops vertices in the middle of straight runs.
+fn simplify(points: Vec<Point>) -> Vec<Point> {
+    let n = points.len();
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = points[(i + n - 1) % n];
+        let curr = points[i];
+        let next = points[(i + 1) % n];
+        let cross = (curr.0 - prev.0) * (next.1 - curr.1) - (curr.1 - prev.1) * (next.0 - curr.0);
+        let dot = (curr.0 - prev.0) * (next.0 - curr.0) + (curr.1 - prev.1) * (next.1 - curr.1);
+        if cross.abs() > MERGE_EPSILON || dot < 0.0 {
+            result.push(curr);
+        }
+    }
+    result
+}
+
+/// Deduplicates nearly equal points so split edges from both inputs share vertices.
+#[derive(Default)]
+struct VertexPool {
+    points: Vec<Point>,
+    grid: FxHashMap<(i64, i64), Vec<usize>>,
+}
+
+impl VertexPool {
+    fn cell(p: Point) -> (i64, i64) {
+        (
+            (p.0 / MERGE_EPSILON).floor() as i64,
+            (p.1 / MERGE_EPSILON).floor() as i64,
+        )
+    }
+
+    fn insert(&mut self, p: Point) -> usize {
+        let (cx, cy) = Self::cell(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(candidates) = self.grid.get(&(cx + dx, cy + dy)) {
+                    for &i in candidates {
+                        let q = self.points[i];
+                        if (q.0 - p.0).hypot(q.1 - p.1) <= MERGE_EPSILON {
+                            return i;
+                        }
+                    }
+                }
+            }
+        }
+        self.points.push(p);
+        let index = self.points.len() - 1;
+        self.grid.entry((cx, cy)).or_default().push(index);
+        index
+    }
+}
@@ -0,0 +1,218 @@
+//! Clipping of tessellated output against a region made of non-overlapping triangles,
+//! as produced by the fill tessellator.
+
+use crate::utils::color::Color;
+
+type Triangle = [(f64, f64); 3];
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vertex {
+    pub x: f64,
+    pub y: f64,
+    pub color: Color,
+}
+
+impl Vertex {
+    fn lerp(&self, other: &Vertex, t: f64) -> Vertex {
+        Vertex {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            color: self.color.mix(other.color, t as f32),
+        }
+    }
+}
+
+/// Area covered by a set of non-overlapping triangles, all stored with the same winding.
+#[derive(Clone, Debug, Default)]
+pub struct ClipRegion {
+    triangles: Vec<Triangle>,
+}
+
+impl ClipRegion {
+    /// Reads the `3.0` triangles of a fill buffer.
+    pub fn from_buffer(buffer: &[f32]) -> Self {
+        let mut triangles = Vec::new();
+        for_each_primitive(buffer, |vertices| {
+            if vertices.len() == 3 {
+                let [a, b, c] = [vertices[0], vertices[1], vertices[2]].map(|v| (v.x, v.y));
+                push_oriented(&mut triangles, [a, b, c]);
+            }
+        });
+        Self { triangles }
+    }
+
+    /// The area covered by both regions, used when `clip` is called with a clip active.
+    pub fn intersect(&self, other: &ClipRegion) -> ClipRegion {
+        let mut triangles = Vec::new();
+        for a in &self.triangles {
+            let polygon: Vec<Vertex> = a
+                .iter()
+                .map(|&(x, y)| Vertex {
+                    x,
+                    y,
+                    color: Color::default(),
+                })
+                .collect();
+            for b in &other.triangles {
+                if !bounds_overlap(a, b) {
+                    continue;
+                }
+                let clipped = clip_polygon(&polygon, b);
+                for i in 1..clipped.len().saturating_sub(1) {
+                    let tri = [clipped[0], clipped[i], clipped[i + 1]].map(|v| (v.x, v.y));
+                    push_oriented(&mut triangles, tri);
+                }
+            }
+        }
+        ClipRegion { triangles }
+    }
+
+    /// Clips every primitive of a Path2D buffer. Primitives fully inside a single clip
+    /// triangle are copied as is, everything else is re-emitted with colors interpolated
+    /// at the new vertices: triangles as `3.0` triangles and quads as `4.0` quads, since
+    /// stroke consumers only draw quads.
+    pub fn clip_buffer(&self, buffer: &[f32]) -> Vec<f32> {
+        let mut output = Vec::with_capacity(buffer.len());
+        for_each_primitive(buffer, |vertices| {
+            if self
+                .triangles
+                .iter()
+                .any(|tri| vertices.iter().all(|v| contains(tri, v.x, v.y)))
+            {
+                push_primitive(&mut output, vertices);
+                return;
+            }
+
+            for i in 1..vertices.len() - 1 {
+                let source = [vertices[0], vertices[i], vertices[i + 1]];
+                let source_bounds = source.map(|v| (v.x, v.y));
+                for tri in &self.triangles {
+                    if !bounds_overlap(&source_bounds, tri) {
+                        continue;
+                    }
+                    let clipped = clip_polygon(&source, tri);
+                    if vertices.len() == 4 {
+                        push_quad_fan(&mut output, &clipped);
+                    } else {
+                        for j in 1..clipped.len().saturating_sub(1) {
+                            push_primitive(&mut output, &[clipped[0], clipped[j], clipped[j + 1]]);
+                        }
+                    }
+                }
+            }
+        });
+        output
+    }
+}
+
+/// Calls `f` with the vertices of every `3.0` triangle and `4.0` quad in a Path2D
+/// buffer. Reading stops at the first unknown marker or at a primitive cut short by
+/// the end of the buffer, so a partially written buffer is skipped, never misread.
+pub fn for_each_primitive(buffer: &[f32], mut f: impl FnMut(&[Vertex])) {
+    let mut i = 0;
+    let mut vertices = Vec::with_capacity(4);
+    while i < buffer.len() {
+        let n = match buffer[i] as i32 {
+            3 => 3,
+            4 => 4,
+            _ => break,
+        };
+        if i + 1 + n * 3 > buffer.len() {
+            break;
+        }
+        let coords = &buffer[i + 1..i + 1 + n * 2];
+        let colors = &buffer[i + 1 + n * 2..i + 1 + n * 3];
+        vertices.clear();
+        vertices.extend((0..n).map(|k| Vertex {
+            x: coords[k * 2] as f64,
+            y: coords[k * 2 + 1] as f64,
+            color: Color::from(colors[k]),
+        }));
+        f(&vertices);
+        i += 1 + n * 3;
+    }
+}
+
+fn push_primitive(buffer: &mut Vec<f32>, vertices: &[Vertex]) {
+    buffer.push(vertices.len() as f32);
+    for v in vertices {
+        buffer.push(v.x as f32);
+        buffer.push(v.y as f32);
+    }
+    for v in vertices {
+        buffer.push(f32::from_bits(v.color.into_raw() as u32));
+    }
+}
+
+/// Splits a convex polygon into a fan of quads, repeating the last vertex when a
+/// single triangle is left over. Any in-order subset of a convex polygon's vertices is
+/// convex, so every quad is too.
+///
+/// Invariant: `polygon` is a source triangle clipped against one clip triangle, and the
+/// intersection of two convex shapes is convex. This breaks if [`ClipRegion`] ever
+/// clips against non-convex pieces; those must be split into triangles first.
+fn push_quad_fan(buffer: &mut Vec<f32>, polygon: &[Vertex]) {
+    let mut j = 1;
+    while j + 1 < polygon.len() {
+        let last = polygon[(j + 2).min(polygon.len() - 1)];
+        push_primitive(buffer, &[polygon[0], polygon[j], polygon[j + 1], last]);
+        j += 2;
+    }
+}
+
+fn cross(a: (f64, f64), b: (f64, f64), x: f64, y: f64) -> f64 {
+    (b.0 - a.0) * (y - a.1) - (b.1 - a.1) * (x - a.0)
+}
+
+/// Stores the triangle with a positive winding so `cross >= 0` means inside.
+fn push_oriented(triangles: &mut Vec<Triangle>, [a, b, c]: Triangle) {
+    let area = cross(a, b, c.0, c.1);
+    if area > 1e-12 {
+        triangles.push([a, b, c]);
+    } else if area < -1e-12 {
+        triangles.push([a, c, b]);
+    }
+}
+
+fn contains(tri: &Triangle, x: f64, y: f64) -> bool {
+    (0..3).all(|i| cross(tri[i], tri[(i + 1) % 3], x, y) >= 0.0)
+}
+
+fn bounds_overlap(a: &Triangle, b: &Triangle) -> bool {
+    let bounds = |t: &Triangle| {
+        t.iter().fold(
+            (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+            |(x0, y0, x1, y1), &(x, y)| (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        )
+    };
+    let (ax0, ay0, ax1, ay1) = bounds(a);
+    let (bx0, by0, bx1, by1) = bounds(b);
+    ax0 <= bx1 && bx0 <= ax1 && ay0 <= by1 && by0 <= ay1
+}
+
+/// Sutherland–Hodgman clipping of a convex polygon against a positively wound triangle.
+fn clip_polygon(polygon: &[Vertex], tri: &Triangle) -> Vec<Vertex> {
+    let mut output = polygon.to_vec();
+    for i in 0..3 {
+        let (a, b) = (tri[i], tri[(i + 1) % 3]);
+        let input = std::mem::take(&mut output);
+        let Some(&last) = input.last() else {
+            break;
+        };
+        let mut prev = last;
+        let mut prev_side = cross(a, b, prev.x, prev.y);
+        for &curr in &input {
+            let side = cross(a, b, curr.x, curr.y);
+            if (side >= 0.0) != (prev_side >= 0.0) {
+                let t = prev_side / (prev_side - side);
+                output.push(prev.lerp(&curr, t));
+            }
+            if side >= 0.0 {
+                output.push(curr);
+            }
+            prev = curr;
+            prev_side = side;
+        }
+    }
+    output
+}
@@ -0,0 +1,132 @@
+//! Splits flattened subpaths into dashes following Canvas `setLineDash` semantics.
+
+use super::{PointData, SegmentData};
+
+/// Patterns shorter than this would split a path into an unbounded number of dashes,
+/// so they stroke solid lines instead.
+const MIN_PATTERN_LENGTH: f64 = 1e-3;
+/// Upper bound of dash boundaries per stroke; longer results fall back to solid lines.
+const MAX_DASH_SPLITS: usize = 1 << 16;
+
+/// Validates a dash pattern like `CanvasRenderingContext2D.setLineDash`.
+/// Returns `None` when the pattern must be ignored, odd-length patterns are repeated once.
+pub fn normalize_pattern(pattern: &[f64]) -> Option<Vec<f64>> {
+    if pattern.iter().any(|v| !v.is_finite() || *v < 0.0) {
+        return None;
+    }
+    let mut result = pattern.to_vec();
+    if !result.len().is_multiple_of(2) {
+        result.extend_from_slice(pattern);
+    }
+    Some(result)
+}
+
+/// An empty, all-zero or vanishingly short pattern strokes solid lines.
+pub fn is_dashed(pattern: &[f64]) -> bool {
+    pattern.iter().sum::<f64>() >= MIN_PATTERN_LENGTH
+}
+
+/// Position inside the dash pattern.
+struct DashState<'a> {
+    pattern: &'a [f64],
+    index: usize,
+    remaining: f64,
+}
+
+impl<'a> DashState<'a> {
+    fn new(pattern: &'a [f64], offset: f64) -> Self {
+        let total: f64 = pattern.iter().sum();
+        let mut phase = offset.rem_euclid(total);
+        let mut index = 0;
+        while phase > pattern[index] {
+            phase -= pattern[index];
+            index = (index + 1) % pattern.len();
+        }
+        Self {
+            pattern,
+            index,
+            remaining: pattern[index] - phase,
+        }
+    }
+
+    fn is_on(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    fn advance(&mut self) {
+        self.index = (self.index + 1) % self.pattern.len();
+        self.remaining = self.pattern[self.index];
+    }
+}
+
+fn lerp_point(a: &PointData, b: &PointData, t: f64) -> PointData {
+    PointData {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+        color: a.color.mix(b.color, t as f32),
+        width: a.width + (b.width - a.width) * t,
+        ..*a
+    }
+}
+
+/// Returns the "on" parts of `segments` as open subpaths. The pattern phase restarts at
+/// `offset` for every subpath and carries over corners, including the closing edge.
+/// `None` when the path would need more than [`MAX_DASH_SPLITS`] dash boundaries.
+pub fn dash_segments(
+    segments: &[SegmentData],
+    pattern: &[f64],
+    offset: f64,
+) -> Option<Vec<SegmentData>> {
+    let mut result = Vec::new();
+    let mut splits = 0;
+
+    for segment in segments {
+        let n = segment.points.len();
+        if n < 2 {
+            continue;
+        }
+        let edge_count = if segment.is_closed { n } else { n - 1 };
+        let mut state = DashState::new(pattern, offset);
+        let mut current: Option<SegmentData> = state.is_on().then(|| SegmentData {
+            points: vec![segment.points[0]],
+            is_closed: false,
+        });
+
+        for i in 0..edge_count {
+            let p0 = &segment.points[i];
+            let p1 = &segment.points[(i + 1) % n];
+            let length = (p1.x - p0.x).hypot(p1.y - p0.y);
+            let mut travelled = 0.0;
+
+            while length - travelled > state.remaining {
+                splits += 1;
+                if splits > MAX_DASH_SPLITS {
+                    return None;
+                }
+                travelled += state.remaining;
+                let split = lerp_point(p0, p1, travelled / length);
+                if let Some(mut dash) = current.take() {
+                    dash.points.push(split);
+                    result.push(dash);
+                }
+                state.advance();
+                if state.is_on() {
+                    current = Some(SegmentData {
+                        points: vec![split],
+                        is_closed: false,
+                    });
+                }
+            }
+
+            state.remaining -= length - travelled;
+            if let Some(dash) = current.as_mut() {
+                dash.points.push(*p1);
+            }
+        }
+
+        if let Some(dash) = current {
+            result.push(dash);
+        }
+    }
+    Some(result)
+}
@@ -0,0 +1,44 @@
+//! Segment counts for flattening curves so that the polyline never deviates from
+//! the real curve by more than a tolerance (in path units).
+
+/// Upper bound per curve to keep degenerate inputs from exploding the buffer.
+const MAX_STEPS: usize = 1024;
+
+pub const DEFAULT_TOLERANCE: f64 = 0.25;
+
+fn clamp_steps(steps: f64) -> usize {
+    if steps.is_finite() {
+        (steps.ceil() as usize).clamp(1, MAX_STEPS)
+    } else {
+        1
+    }
+}
+
+/// Quadratic Bézier: the deviation of `n` uniform segments is `|p0 - 2p1 + p2| / (4n²)`.
+pub fn quadratic_steps(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), tolerance: f64) -> usize {
+    let dd = (p0.0 - 2.0 * p1.0 + p2.0).hypot(p0.1 - 2.0 * p1.1 + p2.1);
+    clamp_steps((dd / (4.0 * tolerance)).sqrt())
+}
+
+/// Cubic Bézier, using Wang's formula.
+pub fn cubic_steps(
+    p0: (f64, f64),
+    p1: (f64, f64),
+    p2: (f64, f64),
+    p3: (f64, f64),
+    tolerance: f64,
+) -> usize {
+    let d1 = (p0.0 - 2.0 * p1.0 + p2.0).hypot(p0.1 - 2.0 * p1.1 + p2.1);
+    let d2 = (p1.0 - 2.0 * p2.0 + p3.0).hypot(p1.1 - 2.0 * p2.1 + p3.1);
+    clamp_steps((0.75 * d1.max(d2) / tolerance).sqrt())
+}
+
+/// Circular arc of `radius` spanning `sweep` radians. For ellipses pass the larger radius.
+pub fn arc_steps(radius: f64, sweep: f64, tolerance: f64) -> usize {
+    let radius = radius.abs();
+    if radius <= tolerance {
+        return clamp_steps(sweep.abs() / std::f64::consts::FRAC_PI_2);
+    }
+    let step = 2.0 * (1.0 - tolerance / radius).acos();
+    clamp_steps(sweep.abs() / step)
+}
@@ -0,0 +1,105 @@
+//! Point containment tests against flattened contours and tessellated buffers.
+
+use super::clip::for_each_primitive;
+
+type Point = (f64, f64);
+
+/// Distance below which a point counts as lying on an edge.
+const EDGE_EPSILON: f64 = 1e-9;
+/// Same for buffer primitives, which only have f32 precision.
+const BUFFER_EPSILON: f64 = 1e-4;
+
+/// Sum of the signed crossings of a rightward ray from `(x, y)`.
+pub fn winding_number(edges: impl Iterator<Item = (Point, Point)>, x: f64, y: f64) -> i32 {
+    let mut winding = 0;
+    for (a, b) in edges {
+        let side = (b.0 - a.0) * (y - a.1) - (b.1 - a.1) * (x - a.0);
+        if a.1 <= y && b.1 > y && side > 0.0 {
+            winding += 1;
+        } else if b.1 <= y && a.1 > y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+pub fn is_inside(winding: i32, even_odd: bool) -> bool {
+    if even_odd {
+        winding % 2 != 0
+    } else {
+        winding != 0
+    }
+}
+
+/// Contours with fewer than three points, or whose points are all collinear,
+/// enclose nothing. Signed area is not enough: a figure eight sums to zero.
+fn encloses_area(contour: &[Point]) -> bool {
+    let Some(&o) = contour.first() else {
+        return false;
+    };
+    contour.windows(2).any(|w| {
+        let (a, b) = (w[0], w[1]);
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0) != 0.0
+    })
+}
+
+fn closed_edges(contours: &[Vec<Point>]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    contours
+        .iter()
+        .filter(|c| encloses_area(c))
+        .flat_map(|c| (0..c.len()).map(move |i| (c[i], c[(i + 1) % c.len()])))
+}
+
+fn distance_to_edge(a: Point, b: Point, x: f64, y: f64) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((x - a.0) * dx + (y - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (x - (a.0 + dx * t)).hypot(y - (a.1 + dy * t))
+}
+
+/// Canvas `isPointInPath`: every contour is implicitly closed and points exactly on
+/// the outline count as inside.
+pub fn contours_contain(contours: &[Vec<Point>], x: f64, y: f64, even_odd: bool) -> bool {
+    if closed_edges(contours).any(|(a, b)| distance_to_edge(a, b, x, y) <= EDGE_EPSILON) {
+        return true;
+    }
+    is_inside(winding_number(closed_edges(contours), x, y), even_odd)
+}
+
+/// Whether any primitive of a Path2D buffer covers `(x, y)`, edges included.
+/// Primitives are convex, so the point must not be on opposite sides of two edges.
+pub fn buffer_contains(buffer: &[f32], x: f64, y: f64) -> bool {
+    let mut hit = false;
+    for_each_primitive(buffer, |vertices| {
+        if hit {
+            return;
+        }
+        let (mut positive, mut negative) = (false, false);
+        for i in 0..vertices.len() {
+            let a = &vertices[i];
+            let b = &vertices[(i + 1) % vertices.len()];
+            let len = (b.x - a.x).hypot(b.y - a.y);
+            if len == 0.0 {
+                continue;
+            }
+            // Signed distance from the edge line.
+            let side = ((b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)) / len;
+            positive |= side > BUFFER_EPSILON;
+            negative |= side < -BUFFER_EPSILON;
+        }
+        let (min_x, max_x) = vertices.iter().fold((f64::MAX, f64::MIN), |(lo, hi), v| {
+            (lo.min(v.x), hi.max(v.x))
+        });
+        let (min_y, max_y) = vertices.iter().fold((f64::MAX, f64::MIN), |(lo, hi), v| {
+            (lo.min(v.y), hi.max(v.y))
+        });
+        let in_bounds = (min_x - BUFFER_EPSILON..=max_x + BUFFER_EPSILON).contains(&x)
+            && (min_y - BUFFER_EPSILON..=max_y + BUFFER_EPSILON).contains(&y);
+        hit = in_bounds && !(positive && negative);
+    });
+    hit
+}
@@ -0,0 +1,89 @@
+//! Corner radii handling for `roundRect`, following the Canvas spec steps.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Radius {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Radii in drawing order, after sign swaps and overlap scaling.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    pub upper_left: Radius,
+    pub upper_right: Radius,
+    pub lower_right: Radius,
+    pub lower_left: Radius,
+}
+
+impl CornerRadii {
+    /// `radii` holds 1 to 4 `(x, y)` pairs. Returns `None` for the cases where Canvas
+    /// throws: a wrong count, or negative / non-finite values.
+    pub fn from_pairs(radii: &[f64], w: f64, h: f64) -> Option<Self> {
+        if !radii.len().is_multiple_of(2) {
+            return None;
+        }
+        let radii: Vec<Radius> = radii
+            .chunks_exact(2)
+            .map(|r| Radius { x: r[0], y: r[1] })
+            .collect();
+        if radii.is_empty()
+            || radii.len() > 4
+            || radii
+                .iter()
+                .any(|r| !r.x.is_finite() || !r.y.is_finite() || r.x < 0.0 || r.y < 0.0)
+        {
+            return None;
+        }
+
+        let (ul, ur, lr, ll) = match radii.as_slice() {
+            [all] => (*all, *all, *all, *all),
+            [a, b] => (*a, *b, *a, *b),
+            [a, b, c] => (*a, *b, *c, *b),
+            [a, b, c, d] => (*a, *b, *c, *d),
+            _ => unreachable!(),
+        };
+        let mut corners = Self {
+            upper_left: ul,
+            upper_right: ur,
+            lower_right: lr,
+            lower_left: ll,
+        };
+
+        if w < 0.0 {
+            std::mem::swap(&mut corners.upper_left, &mut corners.upper_right);
+            std::mem::swap(&mut corners.lower_left, &mut corners.lower_right);
+        }
+        if h < 0.0 {
+            std::mem::swap(&mut corners.upper_left, &mut corners.lower_left);
+            std::mem::swap(&mut corners.upper_right, &mut corners.lower_right);
+        }
+
+        corners.scale_to_fit(w.abs(), h.abs());
+        Some(corners)
+    }
+
+    /// Shrinks all radii by the same factor so that adjacent corners never overlap.
+    fn scale_to_fit(&mut self, w: f64, h: f64) {
+        let top = self.upper_left.x + self.upper_right.x;
+        let right = self.upper_right.y + self.lower_right.y;
+        let bottom = self.lower_right.x + self.lower_left.x;
+        let left = self.upper_left.y + self.lower_left.y;
+        let ratio = |size: f64, sum: f64| if sum > 0.0 { size / sum } else { f64::INFINITY };
+        let scale = ratio(w, top)
+            .min(ratio(h, right))
+            .min(ratio(w, bottom))
+            .min(ratio(h, left));
+
+        if scale < 1.0 {
+            for r in [
+                &mut self.upper_left,
+                &mut self.upper_right,
+                &mut self.lower_right,
+                &mut self.lower_left,
+            ] {
+                r.x *= scale;
+                r.y *= scale;
+            }
+        }
+    }
+}
@@ -0,0 +1,557 @@
+//! SVG path data parsing and a minimal SVG document reader.
+
+use super::Path2D;
+use crate::utils::color::Color;
+use std::f64::consts::{PI, TAU};
+
+/// Appends SVG `d` attribute data to `path`. Like browsers, everything up to the first
+/// error is kept; returns `false` when an error stopped parsing.
+pub fn append_path_data(path: &mut Path2D, data: &str) -> bool {
+    let mut parser = PathDataParser {
+        bytes: data.as_bytes(),
+        pos: 0,
+    };
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // Control point of the previous C/S or Q/T command, for the smooth variants.
+    let mut last_cubic: Option<(f64, f64)> = None;
+    let mut last_quad: Option<(f64, f64)> = None;
+    let mut command: Option<u8> = None;
+
+    loop {
+        parser.skip_separators();
+        let Some(&next) = parser.bytes.get(parser.pos) else {
+            return true;
+        };
+        let cmd = if next.is_ascii_alphabetic() {
+            parser.pos += 1;
+            next
+        } else {
+            // Implicit repetition; extra pairs after a moveto are linetos.
+            match command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(c) if !matches!(c, b'Z' | b'z') => c,
+                _ => return false,
+            }
+        };
+        if command.is_none() && !matches!(cmd, b'M' | b'm') {
+            return false;
+        }
+
+        let relative = cmd.is_ascii_lowercase();
+        let base = if relative { current } else { (0.0, 0.0) };
+        let mut cubic = None;
+        let mut quad = None;
+
+        match cmd.to_ascii_uppercase() {
+            b'M' => {
+                let Some((x, y)) = parser.pair(base) else {
+                    return false;
+                };
+                path.move_to(x, y);
+                current = (x, y);
+                start = current;
+            }
+            b'L' => {
+                let Some((x, y)) = parser.pair(base) else {
+                    return false;
+                };
+                path.line_to(x, y);
+                current = (x, y);
+            }
+            b'H' => {
+                let Some(x) = parser.number() else {
+                    return false;
+                };
+                current.0 = x + base.0;
+                path.line_to(current.0, current.1);
+            }
+            b'V' => {
+                let Some(y) = parser.number() else {
+                    return false;
+                };
+                current.1 = y + base.1;
+                path.line_to(current.0, current.1);
+            }
+            b'C' | b'S' => {
+                let c1 = if cmd.eq_ignore_ascii_case(&b'C') {
+                    match parser.pair(base) {
+                        Some(c1) => c1,
+                        None => return false,
+                    }
+                } else {
+                    reflect(last_cubic, current)
+                };
+                let (Some(c2), Some(end)) = (parser.pair(base), parser.pair(base)) else {
+                    return false;
+                };
+                path.bezier_curve_to(c1.0, c1.1, c2.0, c2.1, end.0, end.1);
+                cubic = Some(c2);
+                current = end;
+            }
+            b'Q' | b'T' => {
+                let c = if cmd.eq_ignore_ascii_case(&b'Q') {
+                    match parser.pair(base) {
+                        Some(c) => c,
+                        None => return false,
+                    }
+                } else {
+                    reflect(last_quad, current)
+                };
+                let Some(end) = parser.pair(base) else {
+                    return false;
+                };
+                path.quadratic_curve_to(c.0, c.1, end.0, end.1);
+                quad = Some(c);
+                current = end;
+            }
+            b'A' => {
+                let (Some(rx), Some(ry), Some(angle)) =
+                    (parser.number(), parser.number(), parser.number())
+                else {
+                    return false;
+                };
+                let (Some(large_arc), Some(sweep)) = (parser.flag(), parser.flag()) else {
+                    return false;
+                };
+                let Some(end) = parser.pair(base) else {
+                    return false;
+                };
+                append_arc(path, current, end, rx, ry, angle, large_arc, sweep);
+                current = end;
+            }
+            b'Z' => {
+                path.close_path();
+                current = start;
+            }
+            _ => return false,
+        }
+
+        last_cubic = cubic;
+        last_quad = quad;
+        command = Some(cmd);
+    }
+}
+
+fn reflect(control: Option<(f64, f64)>, current: (f64, f64)) -> (f64, f64) {
+    match control {
+        Some((x, y)) => (2.0 * current.0 - x, 2.0 * current.1 - y),
+        None => current,
+    }
+}
+
+/// Endpoint to center parameterization from the SVG implementation notes (F.6.5).
+#[allow(clippy::too_many_arguments)]
+fn append_arc(
+    path: &mut Path2D,
+    from: (f64, f64),
+    to: (f64, f64),
+    rx: f64,
+    ry: f64,
+    angle: f64,
+    large_arc: bool,
+    sweep: bool,
+) {
+    if from == to {
+        return;
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        path.line_to(to.0, to.1);
+        return;
+    }
+
+    let phi = angle.to_radians();
+    let (sin, cos) = phi.sin_cos();
+    let dx = (from.0 - to.0) / 2.0;
+    let dy = (from.1 - to.1) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+
+    let cx = cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0;
+
+    let start_angle = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end_angle = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut delta = (end_angle - start_angle) % TAU;
+    if sweep && delta < 0.0 {
+        delta += TAU;
+    } else if !sweep && delta > 0.0 {
+        delta -= TAU;
+    }
+    // A delta of exactly zero would draw a full ellipse.
+    if delta.abs() < 1e-9 {
+        path.line_to(to.0, to.1);
+        return;
+    }
+
+    path.ellipse(
+        cx,
+        cy,
+        rx,
+        ry,
+        phi,
+        start_angle,
+        start_angle + delta,
+        !sweep,
+    );
+}
+
+struct PathDataParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl PathDataParser<'_> {
+    fn skip_separators(&mut self) {
+        while let Some(b) = self.bytes.get(self.pos) {
+            if b.is_ascii_whitespace() || *b == b',' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let start = self.pos;
+        let peek = |p: usize| self.bytes.get(p).copied();
+        let mut end = start;
+        if matches!(peek(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        let digits_start = end;
+        while peek(end).is_some_and(|b| b.is_ascii_digit()) {
+            end += 1;
+        }
+        if peek(end) == Some(b'.') {
+            end += 1;
+            while peek(end).is_some_and(|b| b.is_ascii_digit()) {
+                end += 1;
+            }
+        }
+        if end == digits_start || (end == digits_start + 1 && peek(digits_start) == Some(b'.')) {
+            return None;
+        }
+        if matches!(peek(end), Some(b'e' | b'E')) {
+            let mut exp = end + 1;
+            if matches!(peek(exp), Some(b'+' | b'-')) {
+                exp += 1;
+            }
+            if peek(exp).is_some_and(|b| b.is_ascii_digit()) {
+                while peek(exp).is_some_and(|b| b.is_ascii_digit()) {
+                    exp += 1;
+                }
+                end = exp;
+            }
+        }
+        let value = std::str::from_utf8(&self.bytes[start..end])
+            .ok()?
+            .parse::<f64>()
+            .ok()?;
+        self.pos = end;
+        value.is_finite().then_some(value)
+    }
+
+    fn pair(&mut self, base: (f64, f64)) -> Option<(f64, f64)> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Some((x + base.0, y + base.1))
+    }
+
+    /// Arc flags are single characters and may be written without separators.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.bytes.get(self.pos)? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgShape {
+    Path(String),
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        rx: f64,
+        ry: f64,
+    },
+    Circle {
+        cx: f64,
+        cy: f64,
+        r: f64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgElement {
+    pub shape: SvgShape,
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub stroke_width: f64,
+    pub even_odd: bool,
+}
+
+impl SvgElement {
+    /// Adds the element outline to `path` as new subpaths.
+    pub fn append_to(&self, path: &mut Path2D) {
+        match &self.shape {
+            SvgShape::Path(data) => {
+                append_path_data(path, data);
+            }
+            SvgShape::Rect {
+                x,
+                y,
+                width,
+                height,
+                rx,
+                ry,
+            } => {
+                if *rx > 0.0 && *ry > 0.0 {
+                    let radii = [rx.min(width / 2.0), ry.min(height / 2.0)];
+                    path.round_rect_elliptical(*x, *y, *width, *height, &radii);
+                } else {
+                    path.rect(*x, *y, *width, *height);
+                }
+            }
+            SvgShape::Circle { cx, cy, r } => {
+                path.move_to(cx + r, *cy);
+                path.arc(*cx, *cy, *r, 0.0, 2.0 * PI, false);
+                path.close_path();
+            }
+        }
+    }
+}
+
+/// Reads the `<path>`, `<rect>` and `<circle>` elements of an SVG document in order.
+/// Presentation attributes and the `style` attribute are supported for `fill`, `stroke`,
+/// `stroke-width`, `fill-rule` and opacities; groups, transforms and CSS are ignored.
+pub fn parse_document(document: &str) -> Vec<SvgElement> {
+    let mut elements = Vec::new();
+    let mut rest = document;
+
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(close) = find_tag_end(rest) else {
+            break;
+        };
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let attributes = parse_attributes(&tag[name_end..]);
+        if let Some(element) = build_element(&tag[..name_end], &attributes) {
+            elements.push(element);
+        }
+    }
+    elements
+}
+
+/// Position of the `>` closing a tag, skipping quoted attribute values.
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = source;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim().trim_start_matches('/').trim();
+        let after = rest[eq + 1..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(end) = after[1..].find(quote) else {
+            break;
+        };
+        attributes.push((name.to_string(), after[1..end + 1].to_string()));
+        rest = &after[end + 2..];
+    }
+
+    // Declarations in `style` override presentation attributes.
+    let style: Vec<(String, String)> = attributes
+        .iter()
+        .filter(|(name, _)| name == "style")
+        .flat_map(|(_, value)| value.split(';'))
+        .filter_map(|declaration| {
+            let (name, value) = declaration.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect();
+    attributes.extend(style);
+    attributes
+}
+
+fn build_element(name: &str, attributes: &[(String, String)]) -> Option<SvgElement> {
+    let get = |key: &str| {
+        attributes
+            .iter()
+            .rev()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    };
+    let number = |key: &str| get(key).and_then(parse_length).unwrap_or(0.0);
+
+    let shape = match name {
+        "path" => SvgShape::Path(get("d")?.to_string()),
+        "rect" => {
+            // A missing rx/ry takes the value of the other one.
+            let rx = get("rx").and_then(parse_length);
+            let ry = get("ry").and_then(parse_length);
+            let (width, height) = (number("width"), number("height"));
+            // Zero disables rendering and negative sizes are an error; neither is drawn.
+            if width <= 0.0 || height <= 0.0 {
+                return None;
+            }
+            SvgShape::Rect {
+                x: number("x"),
+                y: number("y"),
+                width,
+                height,
+                rx: rx.or(ry).unwrap_or(0.0),
+                ry: ry.or(rx).unwrap_or(0.0),
+            }
+        }
+        "circle" => {
+            let r = number("r");
+            if r <= 0.0 {
+                return None;
+            }
+            SvgShape::Circle {
+                cx: number("cx"),
+                cy: number("cy"),
+                r,
+            }
+        }
+        _ => return None,
+    };
+
+    let opacity = get("opacity").and_then(parse_opacity).unwrap_or(1.0);
+    let paint = |key: &str, default: Option<Color>, opacity_key: &str| {
+        let color = match get(key) {
+            Some(value) => parse_color(value).unwrap_or(default),
+            None => default,
+        };
+        let alpha = opacity * get(opacity_key).and_then(parse_opacity).unwrap_or(1.0);
+        color.map(|c| with_opacity(c, alpha))
+    };
+
+    Some(SvgElement {
+        shape,
+        fill: paint("fill", Some(Color::new(255, 0, 0, 0)), "fill-opacity"),
+        stroke: paint("stroke", None, "stroke-opacity"),
+        stroke_width: get("stroke-width").and_then(parse_length).unwrap_or(1.0),
+        even_odd: get("fill-rule") == Some("evenodd"),
+    })
+}
+
+/// Numbers with an optional `px` unit; other units are not supported.
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value.strip_suffix("px").unwrap_or(value);
+    value.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// A number or a percentage, e.g. `0.5` or `50%`.
+fn parse_opacity(value: &str) -> Option<f64> {
+    let value = value.trim();
+    match value.strip_suffix('%') {
+        Some(percent) => parse_length(percent).map(|v| v / 100.0),
+        None => parse_length(value),
+    }
+}
+
+fn with_opacity(color: Color, opacity: f64) -> Color {
+    Color {
+        a: (color.a as f64 * opacity.clamp(0.0, 1.0)).round() as u8,
+        ..color
+    }
+}
+
+/// `Some(None)` for `none`, `None` for values that are not understood.
+fn parse_color(value: &str) -> Option<Option<Color>> {
+    let value = value.trim();
+    let color = match value {
+        "none" | "transparent" => return Some(None),
+        "black" => Color::new(255, 0, 0, 0),
+        "white" => Color::new(255, 255, 255, 255),
+        "red" => Color::new(255, 255, 0, 0),
+        "green" => Color::new(255, 0, 128, 0),
+        "blue" => Color::new(255, 0, 0, 255),
+        _ => {
+            if let Some(hex) = value.strip_prefix('#') {
+                parse_hex(hex)?
+            } else {
+                let args = value.strip_prefix("rgb(")?.strip_suffix(')')?;
+                let channels: Vec<u8> = args
+                    .split(',')
+                    .map(|c| {
+                        c.trim()
+                            .parse::<f64>()
+                            .ok()
+                            .map(|v| v.clamp(0.0, 255.0) as u8)
+                    })
+                    .collect::<Option<_>>()?;
+                let [r, g, b] = channels[..] else {
+                    return None;
+                };
+                Color::new(255, r, g, b)
+            }
+        }
+    };
+    Some(Some(color))
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let short = |i: usize| digit(i).map(|d| d * 17);
+            let a = if hex.len() == 4 { short(3)? } else { 255 };
+            Some(Color::new(a, short(0)?, short(1)?, short(2)?))
+        }
+        6 | 8 => {
+            let a = if hex.len() == 8 { byte(6)? } else { 255 };
+            Some(Color::new(a, byte(0)?, byte(2)?, byte(4)?))
+        }
+        _ => None,
+    }
+}
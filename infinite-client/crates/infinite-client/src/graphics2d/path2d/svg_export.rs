@@ -0,0 +1,177 @@
+//! SVG dumps of path segments and tessellation buffers, for inspecting the tessellator
+//! outside the game and for golden-file comparisons. Output is deterministic.
+
+use super::SegmentData;
+use super::clip::for_each_primitive;
+use crate::utils::color::Color;
+use std::fmt::Write;
+
+/// Color of the segment outlines drawn over the buffer.
+const SEGMENT_COLOR: &str = "#ff00ff";
+
+pub fn export(segments: Option<&[SegmentData]>, buffer: Option<&[f32]>) -> String {
+    let mut bounds = Bounds::default();
+    let mut body = String::new();
+
+    if let Some(buffer) = buffer {
+        body.push_str("  <g id=\"buffer\">\n");
+        // Stops at a truncated trailing primitive instead of misreading it.
+        for_each_primitive(buffer, |vertices| {
+            let class = if vertices.len() == 3 {
+                "triangle"
+            } else {
+                "quad"
+            };
+            let points: Vec<String> = vertices
+                .iter()
+                .map(|v| {
+                    bounds.add(v.x, v.y);
+                    format!("{},{}", number(v.x), number(v.y))
+                })
+                .collect();
+            let color = average(vertices.iter().map(|v| v.color));
+            let _ = writeln!(
+                body,
+                "    <polygon class=\"{}\" points=\"{}\" fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"{}\"/>",
+                class,
+                points.join(" "),
+                color.r,
+                color.g,
+                color.b,
+                number(color.a as f64 / 255.0),
+            );
+        });
+        body.push_str("  </g>\n");
+    }
+
+    if let Some(segments) = segments {
+        body.push_str("  <g id=\"segments\">\n");
+        for segment in segments.iter().filter(|s| !s.points.is_empty()) {
+            let mut d = String::new();
+            for (i, p) in segment.points.iter().enumerate() {
+                bounds.add(p.x, p.y);
+                let command = if i == 0 { 'M' } else { 'L' };
+                let _ = write!(d, "{}{} {} ", command, number(p.x), number(p.y));
+            }
+            if segment.is_closed {
+                d.push('Z');
+            }
+            let _ = writeln!(
+                body,
+                "    <path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>",
+                d.trim_end(),
+                SEGMENT_COLOR,
+            );
+        }
+        body.push_str("  </g>\n");
+    }
+
+    let (x, y, w, h) = bounds.view_box();
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n{}</svg>\n",
+        number(x),
+        number(y),
+        number(w),
+        number(h),
+        body
+    )
+}
+
+/// Primitives only carry per-vertex colors; SVG polygons get their average.
+fn average(colors: impl Iterator<Item = Color>) -> Color {
+    let mut sum = [0u32; 4];
+    let mut count = 0;
+    for c in colors {
+        sum[0] += c.a as u32;
+        sum[1] += c.r as u32;
+        sum[2] += c.g as u32;
+        sum[3] += c.b as u32;
+        count += 1;
+    }
+    if count == 0 {
+        return Color::default();
+    }
+    let [a, r, g, b] = sum.map(|v| ((v + count / 2) / count) as u8);
+    Color::new(a, r, g, b)
+}
+
+/// At most three decimals without trailing zeros, so golden files stay stable.
+fn number(value: f64) -> String {
+    let rounded = (value * 1000.0).round() / 1000.0;
+    let text = format!("{:.3}", if rounded == 0.0 { 0.0 } else { rounded });
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[derive(Default)]
+struct Bounds {
+    min: Option<(f64, f64)>,
+    max: (f64, f64),
+}
+
+impl Bounds {
+    fn add(&mut self, x: f64, y: f64) {
+        match self.min {
+            Some((min_x, min_y)) => {
+                self.min = Some((min_x.min(x), min_y.min(y)));
+                self.max = (self.max.0.max(x), self.max.1.max(y));
+            }
+            None => {
+                self.min = Some((x, y));
+                self.max = (x, y);
+            }
+        }
+    }
+
+    /// Bounding box with a one unit margin.
+    fn view_box(&self) -> (f64, f64, f64, f64) {
+        match self.min {
+            Some((x, y)) => (x - 1.0, y - 1.0, self.max.0 - x + 2.0, self.max.1 - y + 2.0),
+            None => (0.0, 0.0, 1.0, 1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Path2D, XrossLineCap, XrossLineJoin};
+    use super::*;
+
+    #[test]
+    fn stroke_matches_golden() {
+        let mut path = Path2D::new();
+        path.set_pen(
+            2.0,
+            0xFFFF0000u32 as i32,
+            XrossLineCap::Butt,
+            XrossLineJoin::Miter,
+            false,
+        );
+        path.move_to(0.0, 0.0);
+        path.line_to(10.0, 0.0);
+        path.line_to(20.0, 10.0 / 3.0);
+        path.tessellate_stroke();
+        assert_eq!(
+            path.export_svg(true, true),
+            include_str!("testdata/stroke.svg")
+        );
+    }
+
+    #[test]
+    fn truncated_primitive_is_skipped() {
+        let quad = [
+            4.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0,
+        ];
+        let mut buffer = quad.to_vec();
+        buffer.extend_from_slice(&quad[..7]);
+        let svg = export(None, Some(&buffer));
+        assert_eq!(svg.matches("<polygon").count(), 1);
+    }
+
+    #[test]
+    fn number_rounding() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(-0.0004), "0");
+        assert_eq!(number(2.0 / 3.0), "0.667");
+        assert_eq!(number(10.5), "10.5");
+    }
+}
@@ -1,9 +1,15 @@
 pub mod block_mesh;
+pub mod chunk_grid;
+pub mod entity_box;
 pub mod generator;
 pub mod infinite_mesh;
+pub mod tracer;
 pub mod types;
 
 pub use block_mesh::*;
+pub use chunk_grid::*;
+pub use entity_box::*;
 pub use generator::*;
 pub use infinite_mesh::*;
+pub use tracer::*;
 pub use types::*;
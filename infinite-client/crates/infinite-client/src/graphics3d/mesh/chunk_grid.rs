@@ -0,0 +1,165 @@
+use super::infinite_mesh::InfiniteMesh;
+use super::types::{ChunkPos, Line};
+use glam::DVec3;
+use xross_core::{XrossClass, xross_methods};
+
+const DEFAULT_BORDER_COLOR: i32 = 0xFFFF0000u32 as i32;
+const DEFAULT_SECTION_COLOR: i32 = 0x800000FFu32 as i32;
+const DEFAULT_CURRENT_COLOR: i32 = 0xFFFFFF00u32 as i32;
+
+/// Builds chunk column borders and 16x16x16 section boundaries around the player,
+/// similar to the vanilla chunk border debug renderer.
+#[derive(XrossClass)]
+pub struct ChunkGridGenerator {
+    border_color: i32,
+    section_color: i32,
+    current_color: i32,
+    min_y: i32,
+    max_y: i32,
+    section_lines: bool,
+    vertical_sections: i32,
+}
+
+impl Default for ChunkGridGenerator {
+    fn default() -> Self {
+        Self {
+            border_color: DEFAULT_BORDER_COLOR,
+            section_color: DEFAULT_SECTION_COLOR,
+            current_color: DEFAULT_CURRENT_COLOR,
+            min_y: -64,
+            max_y: 320,
+            section_lines: true,
+            vertical_sections: 0,
+        }
+    }
+}
+
+#[xross_methods]
+impl ChunkGridGenerator {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn set_colors(&mut self, border_color: i32, section_color: i32, current_color: i32) {
+        self.border_color = border_color;
+        self.section_color = section_color;
+        self.current_color = current_color;
+    }
+
+    /// World height range, `max_y` is exclusive like `Level.getMaxY() + 1`.
+    #[xross_method(critical)]
+    pub fn set_height(&mut self, min_y: i32, max_y: i32) {
+        self.min_y = min_y;
+        self.max_y = max_y.max(min_y);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_section_lines(&mut self, enabled: bool) {
+        self.section_lines = enabled;
+    }
+
+    /// Limits section layer lines to this many sections above and below the player.
+    /// `0` draws every layer of the world.
+    #[xross_method(critical)]
+    pub fn set_vertical_sections(&mut self, sections: i32) {
+        self.vertical_sections = sections.max(0);
+    }
+
+    #[xross_method(panicable)]
+    pub fn generate(
+        &self,
+        mesh: &mut InfiniteMesh,
+        player_x: f64,
+        player_y: f64,
+        player_z: f64,
+        radius: i32,
+    ) {
+        let radius = radius.max(0);
+        let center = ChunkPos::from_block_coords(player_x.floor() as i32, player_z.floor() as i32);
+        let (center_x, center_z) = (center.x, center.z);
+        let min_y = self.min_y as f64;
+        let max_y = self.max_y as f64;
+
+        // Chunk column corners, one vertical line per grid intersection.
+        for cx in (center_x - radius)..=(center_x + radius + 1) {
+            for cz in (center_z - radius)..=(center_z + radius + 1) {
+                let is_current = (cx == center_x || cx == center_x + 1)
+                    && (cz == center_z || cz == center_z + 1);
+                let color = if is_current {
+                    self.current_color
+                } else {
+                    self.border_color
+                };
+                let x = (cx << 4) as f64;
+                let z = (cz << 4) as f64;
+                mesh.push_line(&Line {
+                    start: DVec3::new(x, min_y, z),
+                    end: DVec3::new(x, max_y, z),
+                    color,
+                });
+            }
+        }
+
+        if !self.section_lines {
+            return;
+        }
+
+        let x0 = ((center_x - radius) << 4) as f64;
+        let x1 = ((center_x + radius + 1) << 4) as f64;
+        let z0 = ((center_z - radius) << 4) as f64;
+        let z1 = ((center_z + radius + 1) << 4) as f64;
+        let (layer_min, layer_max) = if self.vertical_sections > 0 {
+            let center_y = (player_y.floor() as i32) >> 4;
+            (
+                ((center_y - self.vertical_sections) << 4).max(self.min_y),
+                ((center_y + self.vertical_sections + 1) << 4).min(self.max_y),
+            )
+        } else {
+            (self.min_y, self.max_y)
+        };
+        let first_layer = layer_min.div_euclid(16) * 16;
+
+        for y in (first_layer..=layer_max).step_by(16) {
+            if y < layer_min {
+                continue;
+            }
+            let y = y as f64;
+
+            for cx in (center_x - radius)..=(center_x + radius + 1) {
+                let x = (cx << 4) as f64;
+                mesh.push_line(&Line {
+                    start: DVec3::new(x, y, z0),
+                    end: DVec3::new(x, y, z1),
+                    color: self.section_color,
+                });
+            }
+            for cz in (center_z - radius)..=(center_z + radius + 1) {
+                let z = (cz << 4) as f64;
+                mesh.push_line(&Line {
+                    start: DVec3::new(x0, y, z),
+                    end: DVec3::new(x1, y, z),
+                    color: self.section_color,
+                });
+            }
+
+            // Outline of the current chunk on this layer, drawn last so it stays on top.
+            let cx0 = center.min_block_x() as f64;
+            let cz0 = center.min_block_z() as f64;
+            let corners = [
+                DVec3::new(cx0, y, cz0),
+                DVec3::new(cx0 + 16.0, y, cz0),
+                DVec3::new(cx0 + 16.0, y, cz0 + 16.0),
+                DVec3::new(cx0, y, cz0 + 16.0),
+            ];
+            for i in 0..4 {
+                mesh.push_line(&Line {
+                    start: corners[i],
+                    end: corners[(i + 1) % 4],
+                    color: self.current_color,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use super::infinite_mesh::InfiniteMesh;
+use super::types::{Direction, Line, Quad};
+use glam::DVec3;
+use rustc_hash::FxHashMap;
+use xross_core::{XrossClass, xross_methods};
+
+/// Number of f64 values describing one entity in [`EntityBoxBuilder::add_entities`].
+///
+/// `class_id, prev_x, prev_y, prev_z, x, y, z,
+///  min_dx, min_dy, min_dz, max_dx, max_dy, max_dz,
+///  eye_height, look_x, look_y, look_z`
+///
+/// The box offsets are relative to the entity position, and a zero look vector
+/// disables the look-direction line for that entity.
+pub const ENTITY_STRIDE: usize = 17;
+
+const DEFAULT_OUTLINE_COLOR: i32 = 0xFFFFFFFFu32 as i32;
+const DEFAULT_FILL_COLOR: i32 = 0x40FFFFFF;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EntityBox {
+    pub class_id: i32,
+    pub prev_pos: DVec3,
+    pub pos: DVec3,
+    pub min: DVec3,
+    pub max: DVec3,
+    pub eye_height: f64,
+    pub look: DVec3,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EntityClassStyle {
+    pub outline_color: i32,
+    pub fill_color: i32,
+}
+
+impl Default for EntityClassStyle {
+    fn default() -> Self {
+        Self {
+            outline_color: DEFAULT_OUTLINE_COLOR,
+            fill_color: DEFAULT_FILL_COLOR,
+        }
+    }
+}
+
+#[derive(XrossClass, Default)]
+pub struct EntityBoxBuilder {
+    entities: Vec<EntityBox>,
+    styles: FxHashMap<i32, EntityClassStyle>,
+}
+
+#[xross_methods]
+impl EntityBoxBuilder {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    #[xross_method(critical)]
+    pub fn set_class_color(&mut self, class_id: i32, outline_color: i32, fill_color: i32) {
+        self.styles.insert(
+            class_id,
+            EntityClassStyle {
+                outline_color,
+                fill_color,
+            },
+        );
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_class_colors(&mut self) {
+        self.styles.clear();
+    }
+
+    #[xross_method]
+    pub fn add_entities(&mut self, data: &[f64]) {
+        self.entities.reserve(data.len() / ENTITY_STRIDE);
+        for e in data.chunks_exact(ENTITY_STRIDE) {
+            self.entities.push(EntityBox {
+                class_id: e[0] as i32,
+                prev_pos: DVec3::new(e[1], e[2], e[3]),
+                pos: DVec3::new(e[4], e[5], e[6]),
+                min: DVec3::new(e[7], e[8], e[9]),
+                max: DVec3::new(e[10], e[11], e[12]),
+                eye_height: e[13],
+                look: DVec3::new(e[14], e[15], e[16]),
+            });
+        }
+    }
+
+    #[xross_method(panicable)]
+    pub fn build(
+        &self,
+        mesh: &mut InfiniteMesh,
+        partial_tick: f64,
+        outline: bool,
+        fill: bool,
+        look_length: f64,
+    ) {
+        let t = partial_tick.clamp(0.0, 1.0);
+        for entity in &self.entities {
+            let style = self.style(entity.class_id);
+            let pos = entity.prev_pos.lerp(entity.pos, t);
+            let min = pos + entity.min;
+            let max = pos + entity.max;
+
+            if outline {
+                for line in box_lines(min, max, style.outline_color) {
+                    mesh.push_line(&line);
+                }
+            }
+            if fill {
+                for quad in box_quads(min, max, style.fill_color) {
+                    mesh.push_quad(&quad);
+                }
+            }
+            if look_length > 0.0 && entity.look != DVec3::ZERO {
+                let start = pos + DVec3::new(0.0, entity.eye_height, 0.0);
+                mesh.push_line(&Line {
+                    start,
+                    end: start + entity.look.normalize() * look_length,
+                    color: style.outline_color,
+                });
+            }
+        }
+    }
+}
+
+impl EntityBoxBuilder {
+    pub fn push(&mut self, entity: EntityBox) {
+        self.entities.push(entity);
+    }
+
+    fn style(&self, class_id: i32) -> EntityClassStyle {
+        self.styles.get(&class_id).copied().unwrap_or_default()
+    }
+}
+
+/// The 12 edges of an axis-aligned box.
+pub fn box_lines(min: DVec3, max: DVec3, color: i32) -> [Line; 12] {
+    let corner = |x: bool, y: bool, z: bool| {
+        DVec3::new(
+            if x { max.x } else { min.x },
+            if y { max.y } else { min.y },
+            if z { max.z } else { min.z },
+        )
+    };
+    let line = |start: DVec3, end: DVec3| Line { start, end, color };
+    [
+        // X axis
+        line(corner(false, false, false), corner(true, false, false)),
+        line(corner(false, true, false), corner(true, true, false)),
+        line(corner(false, false, true), corner(true, false, true)),
+        line(corner(false, true, true), corner(true, true, true)),
+        // Y axis
+        line(corner(false, false, false), corner(false, true, false)),
+        line(corner(true, false, false), corner(true, true, false)),
+        line(corner(false, false, true), corner(false, true, true)),
+        line(corner(true, false, true), corner(true, true, true)),
+        // Z axis
+        line(corner(false, false, false), corner(false, false, true)),
+        line(corner(true, false, false), corner(true, false, true)),
+        line(corner(false, true, false), corner(false, true, true)),
+        line(corner(true, true, false), corner(true, true, true)),
+    ]
+}
+
+/// The 6 outward-facing faces of an axis-aligned box, wound like
+/// [`super::BlockMeshGenerator`] quads.
+pub fn box_quads(min: DVec3, max: DVec3, color: i32) -> [Quad; 6] {
+    let (x0, y0, z0) = (min.x, min.y, min.z);
+    let (x1, y1, z1) = (max.x, max.y, max.z);
+    let quad = |dir: Direction, v: [(f64, f64, f64); 4]| {
+        let (nx, ny, nz) = dir.step();
+        Quad {
+            v1: v[0].into(),
+            v2: v[1].into(),
+            v3: v[2].into(),
+            v4: v[3].into(),
+            color,
+            normal: (nx as f32, ny as f32, nz as f32),
+        }
+    };
+    [
+        quad(
+            Direction::Down,
+            [(x0, y0, z0), (x1, y0, z0), (x1, y0, z1), (x0, y0, z1)],
+        ),
+        quad(
+            Direction::Up,
+            [(x0, y1, z0), (x0, y1, z1), (x1, y1, z1), (x1, y1, z0)],
+        ),
+        quad(
+            Direction::North,
+            [(x0, y0, z0), (x0, y1, z0), (x1, y1, z0), (x1, y0, z0)],
+        ),
+        quad(
+            Direction::South,
+            [(x0, y0, z1), (x1, y0, z1), (x1, y1, z1), (x0, y1, z1)],
+        ),
+        quad(
+            Direction::West,
+            [(x0, y0, z0), (x0, y0, z1), (x0, y1, z1), (x0, y1, z0)],
+        ),
+        quad(
+            Direction::East,
+            [(x1, y0, z0), (x1, y1, z0), (x1, y1, z1), (x1, y0, z1)],
+        ),
+    ]
+}
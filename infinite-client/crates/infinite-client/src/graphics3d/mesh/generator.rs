@@ -1,4 +1,6 @@
 use super::types::{Axis, AxisDirection, BlockPos, Direction, Line, Quad};
+use crate::world::BlockSource;
+use crate::world::raycast::{RaycastHit, raycast};
 use glam::DVec3;
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -141,6 +143,34 @@ impl BlockMeshGenerator {
 
         self.lines = self.combine_lines(raw_lines);
     }
+
+    /// Finds the first added block hit by the ray, e.g. the highlighted ore under the crosshair.
+    #[xross_method(critical)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn raycast(
+        &self,
+        origin_x: f64,
+        origin_y: f64,
+        origin_z: f64,
+        dir_x: f64,
+        dir_y: f64,
+        dir_z: f64,
+        max_distance: f64,
+    ) -> RaycastHit {
+        raycast(
+            self,
+            DVec3::new(origin_x, origin_y, origin_z),
+            DVec3::new(dir_x, dir_y, dir_z),
+            max_distance,
+        )
+        .into()
+    }
+}
+
+impl BlockSource for BlockMeshGenerator {
+    fn is_solid(&self, pos: BlockPos) -> bool {
+        self.blocks.contains_key(&pos.pack())
+    }
 }
 
 impl BlockMeshGenerator {
@@ -1,5 +1,10 @@
+use crate::graphics3d::mesh::BlockMesh;
+use crate::graphics3d::mesh::types::{Line, Quad};
 use xross_core::{XrossClass, xross_methods};
 
+/// Line buffer layout: `x1, y1, z1, x2, y2, z2, color` (7 floats per line).
+/// Quad buffer layout: `v1..v4 (xyz each), color` (13 floats per quad).
+/// Colors are stored as the raw bits of the ARGB int.
 #[derive(XrossClass, Default)]
 pub struct InfiniteMesh {
     line_buffer: Vec<f32>,
@@ -13,6 +18,12 @@ impl InfiniteMesh {
         Self::default()
     }
 
+    #[xross_method(critical)]
+    pub fn clear(&mut self) {
+        self.line_buffer.clear();
+        self.quad_buffer.clear();
+    }
+
     #[xross_method(critical)]
     pub fn get_line_buffer_ptr(&self) -> *const f32 {
         self.line_buffer.as_ptr()
@@ -33,3 +44,34 @@ impl InfiniteMesh {
         self.quad_buffer.len()
     }
 }
+
+impl InfiniteMesh {
+    pub fn push_line(&mut self, line: &Line) {
+        self.line_buffer.extend_from_slice(&[
+            line.start.x as f32,
+            line.start.y as f32,
+            line.start.z as f32,
+            line.end.x as f32,
+            line.end.y as f32,
+            line.end.z as f32,
+            f32::from_bits(line.color as u32),
+        ]);
+    }
+
+    pub fn push_quad(&mut self, quad: &Quad) {
+        for v in [quad.v1, quad.v2, quad.v3, quad.v4] {
+            self.quad_buffer
+                .extend_from_slice(&[v.x as f32, v.y as f32, v.z as f32]);
+        }
+        self.quad_buffer.push(f32::from_bits(quad.color as u32));
+    }
+
+    pub fn extend_from_block_mesh(&mut self, mesh: &BlockMesh) {
+        for line in &mesh.lines {
+            self.push_line(line);
+        }
+        for quad in &mesh.quads {
+            self.push_quad(quad);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use super::infinite_mesh::InfiniteMesh;
+use super::types::{Line, Quad};
+use crate::utils::color::Color;
+use glam::DVec3;
+use xross_core::{XrossClass, xross_methods};
+
+/// Distance in front of the camera where tracers start.
+/// Must stay larger than the near plane (0.05) so the start point is never clipped.
+const DEFAULT_NEAR_OFFSET: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TracerTarget {
+    pub pos: DVec3,
+    pub color: i32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TracerGradient {
+    pub near_color: Color,
+    pub far_color: Color,
+    pub near_distance: f64,
+    pub far_distance: f64,
+}
+
+impl TracerGradient {
+    pub fn color_at(&self, distance: f64) -> Color {
+        let range = self.far_distance - self.near_distance;
+        let t = if range.abs() < 1e-6 {
+            if distance >= self.far_distance {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            (distance - self.near_distance) / range
+        };
+        self.near_color.mix(self.far_color, t as f32)
+    }
+}
+
+#[derive(XrossClass)]
+pub struct TracerBuilder {
+    camera_pos: DVec3,
+    forward: DVec3,
+    up: DVec3,
+    near_offset: f64,
+    thickness: f64,
+    gradient: Option<TracerGradient>,
+    targets: Vec<TracerTarget>,
+}
+
+impl Default for TracerBuilder {
+    fn default() -> Self {
+        Self {
+            camera_pos: DVec3::ZERO,
+            forward: DVec3::Z,
+            up: DVec3::Y,
+            near_offset: DEFAULT_NEAR_OFFSET,
+            thickness: 0.0,
+            gradient: None,
+            targets: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl TracerBuilder {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn clear(&mut self) {
+        self.targets.clear();
+    }
+
+    #[xross_method(critical)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_camera(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: f64,
+        forward_x: f64,
+        forward_y: f64,
+        forward_z: f64,
+        up_x: f64,
+        up_y: f64,
+        up_z: f64,
+    ) {
+        self.camera_pos = DVec3::new(x, y, z);
+        self.forward = DVec3::new(forward_x, forward_y, forward_z).normalize_or(DVec3::Z);
+        self.up = DVec3::new(up_x, up_y, up_z).normalize_or(DVec3::Y);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_near_offset(&mut self, offset: f64) {
+        self.near_offset = offset.max(0.0);
+    }
+
+    /// Width of the tracer in blocks. `0` emits plain lines, anything larger
+    /// emits camera-facing ribbons into the quad buffer.
+    #[xross_method(critical)]
+    pub fn set_thickness(&mut self, thickness: f64) {
+        self.thickness = thickness.max(0.0);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_gradient(
+        &mut self,
+        near_color: i32,
+        far_color: i32,
+        near_distance: f64,
+        far_distance: f64,
+    ) {
+        self.gradient = Some(TracerGradient {
+            near_color: Color::from_raw(near_color),
+            far_color: Color::from_raw(far_color),
+            near_distance,
+            far_distance,
+        });
+    }
+
+    #[xross_method(critical)]
+    pub fn disable_gradient(&mut self) {
+        self.gradient = None;
+    }
+
+    /// `positions` holds `x, y, z` triples and `colors` one ARGB color per target.
+    /// If `colors` is shorter, the remaining targets reuse its last color; nothing is
+    /// added when it is empty.
+    #[xross_method]
+    pub fn add_targets(&mut self, positions: &[f64], colors: &[i32]) {
+        let Some(&last) = colors.last() else {
+            return;
+        };
+        for (i, p) in positions.chunks_exact(3).enumerate() {
+            let color = colors.get(i).copied().unwrap_or(last);
+            self.targets.push(TracerTarget {
+                pos: DVec3::new(p[0], p[1], p[2]),
+                color,
+            });
+        }
+    }
+
+    #[xross_method(panicable)]
+    pub fn build(&self, mesh: &mut InfiniteMesh) {
+        let start = self.start_point();
+        for target in &self.targets {
+            let color = match &self.gradient {
+                Some(gradient) => gradient
+                    .color_at(self.camera_pos.distance(target.pos))
+                    .into_raw(),
+                None => target.color,
+            };
+
+            if self.thickness > 0.0 {
+                if let Some(quad) = self.ribbon(start, target.pos, color) {
+                    mesh.push_quad(&quad);
+                }
+            } else {
+                mesh.push_line(&Line {
+                    start,
+                    end: target.pos,
+                    color,
+                });
+            }
+        }
+    }
+}
+
+impl TracerBuilder {
+    pub fn push(&mut self, target: TracerTarget) {
+        self.targets.push(target);
+    }
+
+    pub fn start_point(&self) -> DVec3 {
+        self.camera_pos + self.forward * self.near_offset
+    }
+
+    pub fn right(&self) -> DVec3 {
+        self.forward.cross(self.up).normalize_or(DVec3::X)
+    }
+
+    /// Builds a quad spanning `start..end` whose width faces the camera at both ends.
+    fn ribbon(&self, start: DVec3, end: DVec3, color: i32) -> Option<Quad> {
+        let dir = (end - start).try_normalize()?;
+        let fallback = self.right();
+        let side_at = |p: DVec3| {
+            dir.cross(p - self.camera_pos)
+                .try_normalize()
+                .unwrap_or(fallback)
+                * (self.thickness / 2.0)
+        };
+        let s0 = side_at(start);
+        let s1 = side_at(end);
+        let normal = s0.cross(dir).normalize_or(self.forward);
+
+        Some(Quad {
+            v1: start - s0,
+            v2: start + s0,
+            v3: end + s1,
+            v4: end - s1,
+            color,
+            normal: (normal.x as f32, normal.y as f32, normal.z as f32),
+        })
+    }
+}
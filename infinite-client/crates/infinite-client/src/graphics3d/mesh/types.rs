@@ -13,6 +13,27 @@ pub struct BlockPos {
     pub z: i32,
 }
 
+/// Chunk column coordinates packed like ChunkPos::asLong() (X: low 32bit, Z: high 32bit).
+#[derive(XrossClass, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct ChunkPos {
+    #[xross_field]
+    pub x: i32,
+    #[xross_field]
+    pub z: i32,
+}
+
+/// 16x16x16 section coordinates (X: 22bit, Z: 22bit, Y: 20bit) packed into u64.
+/// This matches SectionPos::asLong().
+#[derive(XrossClass, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct SectionPos {
+    #[xross_field]
+    pub x: i32,
+    #[xross_field]
+    pub y: i32,
+    #[xross_field]
+    pub z: i32,
+}
+
 #[derive(Clone, Copy)]
 pub struct Quad {
     pub v1: DVec3,
@@ -61,6 +82,189 @@ impl BlockPos {
         let (dx, dy, dz) = dir.step();
         Self::new(self.x + dx, self.y + dy, self.z + dz)
     }
+
+    #[inline(always)]
+    pub fn offset(&self, dx: i32, dy: i32, dz: i32) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    #[inline(always)]
+    pub fn chunk_pos(&self) -> ChunkPos {
+        ChunkPos::new(self.x >> 4, self.z >> 4)
+    }
+
+    #[inline(always)]
+    pub fn section_pos(&self) -> SectionPos {
+        SectionPos::new(self.x >> 4, self.y >> 4, self.z >> 4)
+    }
+}
+
+impl ChunkPos {
+    #[inline(always)]
+    pub fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    #[inline(always)]
+    pub fn from_block_coords(x: i32, z: i32) -> Self {
+        Self::new(x >> 4, z >> 4)
+    }
+
+    #[inline(always)]
+    pub fn pack(&self) -> u64 {
+        (self.x as u32 as u64) | ((self.z as u32 as u64) << 32)
+    }
+
+    #[inline(always)]
+    pub fn unpack(val: u64) -> Self {
+        Self::new(val as u32 as i32, (val >> 32) as u32 as i32)
+    }
+
+    pub fn min_block_x(&self) -> i32 {
+        self.x << 4
+    }
+
+    pub fn min_block_z(&self) -> i32 {
+        self.z << 4
+    }
+
+    pub fn max_block_x(&self) -> i32 {
+        (self.x << 4) + 15
+    }
+
+    pub fn max_block_z(&self) -> i32 {
+        (self.z << 4) + 15
+    }
+
+    pub fn section(&self, section_y: i32) -> SectionPos {
+        SectionPos::new(self.x, section_y, self.z)
+    }
+
+    /// Sections of this column from `min_section` to `max_section` (inclusive).
+    pub fn sections(&self, min_section: i32, max_section: i32) -> impl Iterator<Item = SectionPos> {
+        let (x, z) = (self.x, self.z);
+        (min_section..=max_section).map(move |y| SectionPos::new(x, y, z))
+    }
+
+    /// Every block of this column between `min_y` and `max_y` (inclusive).
+    pub fn blocks(&self, min_y: i32, max_y: i32) -> impl Iterator<Item = BlockPos> {
+        let (bx, bz) = (self.min_block_x(), self.min_block_z());
+        (min_y..=max_y).flat_map(move |y| {
+            (0..16).flat_map(move |z| (0..16).map(move |x| BlockPos::new(bx + x, y, bz + z)))
+        })
+    }
+
+    pub fn neighbors(&self) -> [ChunkPos; 4] {
+        [
+            Self::new(self.x, self.z - 1),
+            Self::new(self.x, self.z + 1),
+            Self::new(self.x - 1, self.z),
+            Self::new(self.x + 1, self.z),
+        ]
+    }
+
+    /// Chunks in a square of `radius` around this one, including itself.
+    pub fn range(&self, radius: i32) -> impl Iterator<Item = ChunkPos> {
+        let (cx, cz) = (self.x, self.z);
+        (-radius..=radius)
+            .flat_map(move |dz| (-radius..=radius).map(move |dx| ChunkPos::new(cx + dx, cz + dz)))
+    }
+
+    pub fn chessboard_distance(&self, other: &ChunkPos) -> i32 {
+        (self.x - other.x).abs().max((self.z - other.z).abs())
+    }
+}
+
+impl From<BlockPos> for ChunkPos {
+    fn from(pos: BlockPos) -> Self {
+        pos.chunk_pos()
+    }
+}
+
+impl SectionPos {
+    #[inline(always)]
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    #[inline(always)]
+    pub fn from_block_coords(x: i32, y: i32, z: i32) -> Self {
+        Self::new(x >> 4, y >> 4, z >> 4)
+    }
+
+    #[inline(always)]
+    pub fn pack(&self) -> u64 {
+        ((self.x as u64 & 0x3FFFFF) << 42)
+            | ((self.z as u64 & 0x3FFFFF) << 20)
+            | (self.y as u64 & 0xFFFFF)
+    }
+
+    #[inline(always)]
+    pub fn unpack(val: u64) -> Self {
+        let val = val as i64;
+        let x = (val >> 42) as i32;
+        let z = (val << 22 >> 42) as i32;
+        let y = (val << 44 >> 44) as i32;
+        Self::new(x, y, z)
+    }
+
+    pub fn chunk_pos(&self) -> ChunkPos {
+        ChunkPos::new(self.x, self.z)
+    }
+
+    /// Lowest corner block of this section.
+    pub fn origin(&self) -> BlockPos {
+        BlockPos::new(self.x << 4, self.y << 4, self.z << 4)
+    }
+
+    /// Index of a block inside a 4096-entry section array, `(y << 8) | (z << 4) | x`.
+    #[inline(always)]
+    pub fn local_index(pos: BlockPos) -> usize {
+        (((pos.y & 15) << 8) | ((pos.z & 15) << 4) | (pos.x & 15)) as usize
+    }
+
+    /// Inverse of [`Self::local_index`].
+    #[inline(always)]
+    pub fn block_at(&self, index: usize) -> BlockPos {
+        let index = index as i32;
+        self.origin()
+            .offset(index & 15, (index >> 8) & 15, (index >> 4) & 15)
+    }
+
+    /// All 4096 blocks in section array order.
+    pub fn blocks(&self) -> impl Iterator<Item = BlockPos> {
+        let section = *self;
+        (0..4096).map(move |i| section.block_at(i))
+    }
+
+    pub fn contains(&self, pos: BlockPos) -> bool {
+        pos.section_pos() == *self
+    }
+
+    pub fn relative(&self, dir: Direction) -> Self {
+        let (dx, dy, dz) = dir.step();
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    pub fn neighbors(&self) -> [SectionPos; 6] {
+        Direction::all().map(|dir| self.relative(dir))
+    }
+
+    /// Sections in a cube of `radius` around this one, including itself.
+    pub fn cube(&self, radius: i32) -> impl Iterator<Item = SectionPos> {
+        let s = *self;
+        (-radius..=radius).flat_map(move |dy| {
+            (-radius..=radius).flat_map(move |dz| {
+                (-radius..=radius).map(move |dx| SectionPos::new(s.x + dx, s.y + dy, s.z + dz))
+            })
+        })
+    }
+}
+
+impl From<BlockPos> for SectionPos {
+    fn from(pos: BlockPos) -> Self {
+        pos.section_pos()
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -110,6 +314,33 @@ impl Direction {
             Direction::East,
         ]
     }
+
+    /// Same ordering as Minecraft's `Direction.ordinal()` (3D data value).
+    pub fn ordinal(&self) -> i32 {
+        match self {
+            Direction::Down => 0,
+            Direction::Up => 1,
+            Direction::North => 2,
+            Direction::South => 3,
+            Direction::West => 4,
+            Direction::East => 5,
+        }
+    }
+
+    pub fn from_ordinal(n: i32) -> Option<Self> {
+        Self::all().get(usize::try_from(n).ok()?).copied()
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::East => Direction::West,
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -0,0 +1,116 @@
+use glam::{DVec3, Mat4, Vec4};
+use xross_core::{XrossClass, xross_methods};
+
+/// Number of f32 values written per projected point:
+/// `screen_x, screen_y, depth, flags`.
+pub const PROJECTION_STRIDE: usize = 4;
+
+/// The point is in front of the camera (clip w > 0).
+pub const FLAG_IN_FRONT: u32 = 1;
+/// The projected point lies inside the viewport rectangle.
+pub const FLAG_ON_SCREEN: u32 = 1 << 1;
+/// The point is between the near and far planes.
+pub const FLAG_IN_DEPTH: u32 = 1 << 2;
+pub const FLAG_VISIBLE: u32 = FLAG_IN_FRONT | FLAG_ON_SCREEN | FLAG_IN_DEPTH;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScreenPoint {
+    pub x: f32,
+    pub y: f32,
+    pub depth: f32,
+    pub flags: u32,
+}
+
+impl ScreenPoint {
+    pub fn is_visible(&self) -> bool {
+        self.flags & FLAG_VISIBLE == FLAG_VISIBLE
+    }
+}
+
+/// Batch world-to-screen projection. Screen coordinates have their origin at the
+/// top-left corner of the viewport, depth is mapped to `0.0..=1.0`.
+#[derive(XrossClass, Default)]
+pub struct ScreenProjector {
+    view_projection: Mat4,
+    origin: DVec3,
+    width: f32,
+    height: f32,
+    buffer: Vec<f32>,
+}
+
+#[xross_methods]
+impl ScreenProjector {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `matrix` is a column-major 4x4 matrix as produced by JOML `Matrix4f.get(float[])`.
+    #[xross_method(critical)]
+    pub fn set_view_projection(&mut self, matrix: &[f32], width: f32, height: f32) {
+        if matrix.len() < 16 {
+            return;
+        }
+        self.view_projection = Mat4::from_cols_slice(&matrix[..16]);
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Camera position subtracted from every world position before projecting.
+    /// Minecraft's matrices are camera-relative, so this keeps precision far from the origin.
+    #[xross_method(critical)]
+    pub fn set_origin(&mut self, x: f64, y: f64, z: f64) {
+        self.origin = DVec3::new(x, y, z);
+    }
+
+    /// `positions` holds `x, y, z` triples. Results are written to the buffer,
+    /// [`PROJECTION_STRIDE`] floats per position.
+    #[xross_method]
+    pub fn project(&mut self, positions: &[f64]) {
+        self.buffer.clear();
+        self.buffer.reserve(positions.len() / 3 * PROJECTION_STRIDE);
+        for p in positions.chunks_exact(3) {
+            let point = self.project_point(DVec3::new(p[0], p[1], p[2]));
+            self.buffer.extend_from_slice(&[
+                point.x,
+                point.y,
+                point.depth,
+                f32::from_bits(point.flags),
+            ]);
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_ptr(&self) -> *const f32 {
+        self.buffer.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_buffer_size(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl ScreenProjector {
+    pub fn project_point(&self, pos: DVec3) -> ScreenPoint {
+        let rel = (pos - self.origin).as_vec3();
+        let clip = self.view_projection * Vec4::new(rel.x, rel.y, rel.z, 1.0);
+        if clip.w <= f32::EPSILON {
+            return ScreenPoint::default();
+        }
+
+        let ndc = clip.truncate() / clip.w;
+        let x = (ndc.x * 0.5 + 0.5) * self.width;
+        let y = (0.5 - ndc.y * 0.5) * self.height;
+        let depth = ndc.z * 0.5 + 0.5;
+
+        let mut flags = FLAG_IN_FRONT;
+        if (0.0..=self.width).contains(&x) && (0.0..=self.height).contains(&y) {
+            flags |= FLAG_ON_SCREEN;
+        }
+        if (0.0..=1.0).contains(&depth) {
+            flags |= FLAG_IN_DEPTH;
+        }
+        ScreenPoint { x, y, depth, flags }
+    }
+}
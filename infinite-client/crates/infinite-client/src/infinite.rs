@@ -1,12 +1,14 @@
 use std::sync::LazyLock;
 use xross_core::{XrossClass, xross_methods};
 pub mod feature;
-mod property;
+pub(crate) mod property;
 
+use crate::world::snapshot::WorldSnapshot;
 use feature::FeatureBundler;
 #[derive(XrossClass, Default)]
 pub struct InfiniteClient {
     pub features: FeatureBundler,
+    pub world: WorldSnapshot,
 }
 #[xross_methods]
 impl InfiniteClient {
@@ -16,4 +18,5 @@ impl InfiniteClient {
         println!("Native Infinite Client has initialized.");
     }
 }
-static INFINITE_CLIENT: LazyLock<InfiniteClient> = LazyLock::new(InfiniteClient::default);
+pub(crate) static INFINITE_CLIENT: LazyLock<InfiniteClient> =
+    LazyLock::new(InfiniteClient::default);
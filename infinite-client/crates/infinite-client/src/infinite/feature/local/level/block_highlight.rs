@@ -1,4 +1,5 @@
 use crate::infinite::INFINITE_CLIENT;
+use rustc_hash::FxHashMap;
 use xross_core::{XrossClass, xross_methods};
 mod settings;
 use crate::infinite::property::BlockAndColor;
@@ -20,6 +21,20 @@ impl Default for BlockHighlightFeature {
         }
     }
 }
+impl BlockHighlightFeature {
+    /// Highlighted block ids mapped to their ARGB colors. Native scans read their
+    /// targets from here so they all agree with the highlight list.
+    pub fn targets() -> FxHashMap<i32, i32> {
+        Self::instance()
+            .settings
+            .blocks_to_highlight
+            .read()
+            .iter()
+            .map(|b| (b.id as i32, b.color.into_raw()))
+            .collect()
+    }
+}
+
 // ロジックの実体はすべてこちらに集約する
 impl SettingsSetter for BlockHighlightFeature {
     fn update_highlight_list(buff: &[u64]) {
@@ -1,5 +1,7 @@
 pub mod graphics2d;
 pub mod graphics3d;
 pub mod infinite;
+pub mod mining;
 pub mod projectile;
 pub mod utils;
+pub mod world;
@@ -0,0 +1,2 @@
+pub mod break_time;
+pub mod tunnel;
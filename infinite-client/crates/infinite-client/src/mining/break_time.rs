@@ -0,0 +1,310 @@
+use rustc_hash::FxHashMap;
+use xross_core::{XrossClass, xross_methods};
+
+/// Tool category bits, matching the `mineable/*` block tags.
+pub const TOOL_PICKAXE: i32 = 1;
+pub const TOOL_AXE: i32 = 1 << 1;
+pub const TOOL_SHOVEL: i32 = 1 << 2;
+pub const TOOL_HOE: i32 = 1 << 3;
+pub const TOOL_SWORD: i32 = 1 << 4;
+pub const TOOL_SHEARS: i32 = 1 << 5;
+
+/// Returned by [`BreakTimeCalculator::break_ticks`] for unbreakable or unknown blocks.
+pub const UNBREAKABLE: i32 = -1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockHardness {
+    /// `-1` for unbreakable blocks such as bedrock.
+    pub hardness: f32,
+    /// Tool categories that mine this block faster.
+    pub tool_mask: i32,
+    /// Minimum tool tier needed for drops (0: wood/gold, 1: stone, 2: iron, 3: diamond).
+    pub min_tier: i32,
+    /// `BlockBehaviour.requiresCorrectToolForDrops`.
+    pub requires_tool: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MiningState {
+    pub tool_mask: i32,
+    /// 0: wood/gold, 1: stone, 2: iron, 3: diamond, 4: netherite.
+    pub tool_tier: i32,
+    pub tool_speed: f32,
+    pub efficiency: i32,
+    /// Effect level (amplifier + 1), `0` when absent.
+    pub haste: i32,
+    pub mining_fatigue: i32,
+    /// Submerged without Aqua Affinity.
+    pub underwater: bool,
+    pub on_ground: bool,
+}
+
+impl Default for MiningState {
+    fn default() -> Self {
+        Self {
+            tool_mask: 0,
+            tool_tier: 0,
+            tool_speed: 1.0,
+            efficiency: 0,
+            haste: 0,
+            mining_fatigue: 0,
+            underwater: false,
+            on_ground: true,
+        }
+    }
+}
+
+impl MiningState {
+    pub fn can_harvest(&self, block: &BlockHardness) -> bool {
+        !block.requires_tool
+            || (self.tool_mask & block.tool_mask != 0 && self.tool_tier >= block.min_tier)
+    }
+
+    /// `Player.getDestroySpeed` for `block`.
+    pub fn destroy_speed(&self, block: &BlockHardness) -> f32 {
+        let mut speed = if self.tool_mask & block.tool_mask != 0 {
+            self.tool_speed
+        } else {
+            1.0
+        };
+
+        if speed > 1.0 && self.efficiency > 0 {
+            speed += (self.efficiency * self.efficiency + 1) as f32;
+        }
+        if self.haste > 0 {
+            speed *= 1.0 + self.haste as f32 * 0.2;
+        }
+        if self.mining_fatigue > 0 {
+            speed *= match self.mining_fatigue {
+                1 => 0.3,
+                2 => 0.09,
+                3 => 0.0027,
+                _ => 8.1e-4,
+            };
+        }
+        if self.underwater {
+            speed *= 0.2;
+        }
+        if !self.on_ground {
+            speed /= 5.0;
+        }
+        speed
+    }
+
+    /// `BlockBehaviour.getDestroyProgress`: fraction of the block broken per tick.
+    pub fn destroy_progress(&self, block: &BlockHardness) -> f32 {
+        if block.hardness < 0.0 {
+            return 0.0;
+        }
+        if block.hardness == 0.0 {
+            return f32::INFINITY;
+        }
+        let divisor = if self.can_harvest(block) { 30.0 } else { 100.0 };
+        self.destroy_speed(block) / block.hardness / divisor
+    }
+
+    /// Ticks needed to break `block`. `0` means it breaks instantly on click.
+    pub fn break_ticks(&self, block: &BlockHardness) -> i32 {
+        let progress = self.destroy_progress(block);
+        if progress <= 0.0 {
+            UNBREAKABLE
+        } else if progress >= 1.0 {
+            0
+        } else {
+            (1.0 / progress).ceil() as i32
+        }
+    }
+}
+
+/// Vanilla dig speed calculation. Block data is passed from Kotlin since it
+/// depends on the registry of the running game.
+#[derive(XrossClass, Default)]
+pub struct BreakTimeCalculator {
+    blocks: FxHashMap<i32, BlockHardness>,
+    state: MiningState,
+}
+
+#[xross_methods]
+impl BreakTimeCalculator {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parallel arrays indexed per block id. `requires_tool` is `0` or `1`.
+    #[xross_method]
+    pub fn set_block_data(
+        &mut self,
+        ids: &[i32],
+        hardness: &[f32],
+        tool_masks: &[i32],
+        min_tiers: &[i32],
+        requires_tool: &[i32],
+    ) {
+        self.blocks.clear();
+        for (i, &id) in ids.iter().enumerate() {
+            let (Some(&h), Some(&mask), Some(&tier), Some(&req)) = (
+                hardness.get(i),
+                tool_masks.get(i),
+                min_tiers.get(i),
+                requires_tool.get(i),
+            ) else {
+                break;
+            };
+            self.blocks.insert(
+                id,
+                BlockHardness {
+                    hardness: h,
+                    tool_mask: mask,
+                    min_tier: tier,
+                    requires_tool: req != 0,
+                },
+            );
+        }
+    }
+
+    /// `speed` is the item's base mining speed for matching blocks (e.g. 8.0 for diamond).
+    #[xross_method(critical)]
+    pub fn set_tool(&mut self, tool_mask: i32, tier: i32, speed: f32) {
+        self.state.tool_mask = tool_mask;
+        self.state.tool_tier = tier;
+        self.state.tool_speed = speed;
+    }
+
+    #[xross_method(critical)]
+    pub fn set_efficiency(&mut self, level: i32) {
+        self.state.efficiency = level.max(0);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_effects(&mut self, haste: i32, mining_fatigue: i32) {
+        self.state.haste = haste.max(0);
+        self.state.mining_fatigue = mining_fatigue.max(0);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_environment(&mut self, underwater: bool, on_ground: bool) {
+        self.state.underwater = underwater;
+        self.state.on_ground = on_ground;
+    }
+
+    #[xross_method(critical)]
+    pub fn destroy_progress(&self, id: i32) -> f32 {
+        self.blocks
+            .get(&id)
+            .map_or(0.0, |block| self.state.destroy_progress(block))
+    }
+
+    /// Ticks to break block `id`, `0` for instant breaks and [`UNBREAKABLE`]
+    /// for unbreakable or unknown ids.
+    #[xross_method(critical)]
+    pub fn break_ticks(&self, id: i32) -> i32 {
+        self.blocks
+            .get(&id)
+            .map_or(UNBREAKABLE, |block| self.state.break_ticks(block))
+    }
+
+    #[xross_method(critical)]
+    pub fn is_instant(&self, id: i32) -> bool {
+        self.break_ticks(id) == 0
+    }
+
+    #[xross_method(critical)]
+    pub fn can_harvest(&self, id: i32) -> bool {
+        self.blocks
+            .get(&id)
+            .is_some_and(|block| self.state.can_harvest(block))
+    }
+}
+
+impl BreakTimeCalculator {
+    pub fn insert_block(&mut self, id: i32, block: BlockHardness) {
+        self.blocks.insert(id, block);
+    }
+
+    pub fn state(&self) -> &MiningState {
+        &self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STONE: i32 = 1;
+    const OBSIDIAN: i32 = 2;
+    const DIRT: i32 = 3;
+    const BEDROCK: i32 = 4;
+    const TORCH: i32 = 5;
+    const LOG: i32 = 6;
+
+    fn calculator() -> BreakTimeCalculator {
+        let mut calc = BreakTimeCalculator::new();
+        calc.set_block_data(
+            &[STONE, OBSIDIAN, DIRT, BEDROCK, TORCH, LOG],
+            &[1.5, 50.0, 0.5, -1.0, 0.0, 2.0],
+            &[TOOL_PICKAXE, TOOL_PICKAXE, TOOL_SHOVEL, 0, 0, TOOL_AXE],
+            &[0, 3, 0, 0, 0, 0],
+            &[1, 1, 0, 0, 0, 0],
+        );
+        calc
+    }
+
+    #[test]
+    fn bare_hand() {
+        let calc = calculator();
+        assert_eq!(calc.break_ticks(STONE), 150);
+        assert_eq!(calc.break_ticks(DIRT), 15);
+        assert_eq!(calc.break_ticks(LOG), 60);
+        assert_eq!(calc.break_ticks(TORCH), 0);
+        assert_eq!(calc.break_ticks(BEDROCK), UNBREAKABLE);
+        assert_eq!(calc.break_ticks(99), UNBREAKABLE);
+        assert!(!calc.can_harvest(STONE));
+    }
+
+    #[test]
+    fn tools() {
+        let mut calc = calculator();
+        calc.set_tool(TOOL_PICKAXE, 0, 2.0);
+        assert_eq!(calc.break_ticks(STONE), 23);
+        calc.set_tool(TOOL_PICKAXE, 3, 8.0);
+        assert_eq!(calc.break_ticks(STONE), 6);
+        assert_eq!(calc.break_ticks(OBSIDIAN), 188);
+        // An iron pickaxe cannot harvest obsidian, so the slower divisor applies.
+        calc.set_tool(TOOL_PICKAXE, 2, 6.0);
+        assert!(!calc.can_harvest(OBSIDIAN));
+        assert_eq!(calc.break_ticks(OBSIDIAN), 834);
+    }
+
+    #[test]
+    fn efficiency_and_haste() {
+        let mut calc = calculator();
+        calc.set_tool(TOOL_PICKAXE, 4, 9.0);
+        calc.set_efficiency(5);
+        assert_eq!(calc.break_ticks(STONE), 2);
+        calc.set_effects(2, 0);
+        assert!(calc.is_instant(STONE));
+    }
+
+    #[test]
+    fn mining_fatigue() {
+        let mut calc = calculator();
+        calc.set_tool(TOOL_PICKAXE, 3, 8.0);
+        calc.set_effects(0, 1);
+        assert_eq!(calc.break_ticks(STONE), 19);
+        calc.set_effects(0, 3);
+        assert_eq!(calc.break_ticks(STONE), 2084);
+    }
+
+    #[test]
+    fn underwater_and_airborne() {
+        let mut calc = calculator();
+        calc.set_tool(TOOL_PICKAXE, 3, 8.0);
+        calc.set_environment(true, true);
+        assert_eq!(calc.break_ticks(STONE), 29);
+        calc.set_environment(false, false);
+        assert_eq!(calc.break_ticks(STONE), 29);
+        calc.set_environment(true, false);
+        assert_eq!(calc.break_ticks(STONE), 141);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::graphics3d::mesh::{BlockPos, Direction};
+use crate::world::snapshot::{WorldSnapshot, WorldView};
+use glam::DVec3;
+use rustc_hash::FxHashSet;
+use std::f64::consts::PI;
+use xross_core::{XrossClass, xross_methods};
+
+/// Number of f32 values written per planned block in the rotation buffer:
+/// `yaw, pitch, flags`.
+pub const TUNNEL_STRIDE: usize = 3;
+
+/// A fluid touches the block, breaking it will let the fluid into the tunnel.
+pub const FLAG_FLOOD: u32 = 1;
+/// A falling block (sand, gravel...) rests on top of the block.
+pub const FLAG_COLLAPSE: u32 = 1 << 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlannedBreak {
+    pub pos: BlockPos,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub flags: u32,
+}
+
+/// Cross-section of the tunnel as `(lateral, vertical)` offsets from the block in
+/// front of the player's feet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TunnelProfile {
+    pub cells: Vec<(i32, i32)>,
+}
+
+impl TunnelProfile {
+    /// `width` is centered on the player, `height` grows upward from the feet level.
+    pub fn rect(width: i32, height: i32) -> Self {
+        let width = width.max(1);
+        let left = -(width - 1) / 2;
+        let mut cells = Vec::new();
+        for v in (0..height.max(1)).rev() {
+            for u in left..left + width {
+                cells.push((u, v));
+            }
+        }
+        Self { cells }
+    }
+}
+
+impl Default for TunnelProfile {
+    fn default() -> Self {
+        Self::rect(1, 2)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TunnelRules {
+    pub fluids: FxHashSet<i32>,
+    pub falling: FxHashSet<i32>,
+}
+
+/// Plans the blocks to break along `dir`, ordered slice by slice away from the
+/// player and top to bottom inside a slice.
+#[allow(clippy::too_many_arguments)]
+pub fn plan_tunnel(
+    view: &WorldView,
+    rules: &TunnelRules,
+    profile: &TunnelProfile,
+    feet: BlockPos,
+    eye: DVec3,
+    dir: Direction,
+    reach: f64,
+    length: i32,
+) -> Vec<PlannedBreak> {
+    let (fx, fy, fz) = dir.step();
+    // Lateral and vertical axes of the cross-section.
+    let (right, up) = match dir {
+        Direction::North => ((1, 0, 0), (0, 1, 0)),
+        Direction::South => ((-1, 0, 0), (0, 1, 0)),
+        Direction::West => ((0, 0, -1), (0, 1, 0)),
+        Direction::East => ((0, 0, 1), (0, 1, 0)),
+        Direction::Up | Direction::Down => ((1, 0, 0), (0, 0, 1)),
+    };
+
+    let mut cells = Vec::new();
+    for step in 1..=length.max(0) {
+        for &(u, v) in &profile.cells {
+            cells.push(feet.offset(
+                fx * step + right.0 * u + up.0 * v,
+                fy * step + right.1 * u + up.1 * v,
+                fz * step + right.2 * u + up.2 * v,
+            ));
+        }
+    }
+    let tunnel: FxHashSet<BlockPos> = cells.iter().copied().collect();
+
+    let reach_sq = reach * reach;
+    let mut planned = Vec::new();
+    for pos in cells {
+        let center = DVec3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.5, pos.z as f64 + 0.5);
+        if center.distance_squared(eye) > reach_sq {
+            continue;
+        }
+        let Some(id) = view.block(pos) else {
+            continue;
+        };
+        if view.is_air_id(id) || rules.fluids.contains(&id) {
+            continue;
+        }
+
+        let mut flags = 0;
+        for side in Direction::all() {
+            let neighbor = pos.relative(side);
+            if tunnel.contains(&neighbor) {
+                continue;
+            }
+            if let Some(nid) = view.block(neighbor) {
+                if rules.fluids.contains(&nid) {
+                    flags |= FLAG_FLOOD;
+                }
+                if side == Direction::Up && rules.falling.contains(&nid) {
+                    flags |= FLAG_COLLAPSE;
+                }
+            }
+        }
+
+        let (yaw, pitch) = look_rotation(eye, center);
+        planned.push(PlannedBreak {
+            pos,
+            yaw,
+            pitch,
+            flags,
+        });
+    }
+    planned
+}
+
+/// Minecraft yaw/pitch in degrees to look from `eye` at `target`.
+pub fn look_rotation(eye: DVec3, target: DVec3) -> (f32, f32) {
+    let d = target - eye;
+    let yaw = (-d.x).atan2(d.z) * (180.0 / PI);
+    let pitch = -(d.y.atan2(d.x.hypot(d.z))) * (180.0 / PI);
+    (yaw as f32, pitch as f32)
+}
+
+#[derive(XrossClass)]
+pub struct TunnelPlanner {
+    rules: TunnelRules,
+    profile: TunnelProfile,
+    direction: Direction,
+    positions: Vec<u64>,
+    rotations: Vec<f32>,
+}
+
+impl Default for TunnelPlanner {
+    fn default() -> Self {
+        Self {
+            rules: TunnelRules::default(),
+            profile: TunnelProfile::default(),
+            direction: Direction::North,
+            positions: Vec::new(),
+            rotations: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl TunnelPlanner {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rectangular profile such as 1x2 or 3x3.
+    #[xross_method(critical)]
+    pub fn set_profile(&mut self, width: i32, height: i32) {
+        self.profile = TunnelProfile::rect(width, height);
+    }
+
+    /// `offsets` holds `(lateral, vertical)` pairs, listed in break order.
+    #[xross_method]
+    pub fn set_custom_profile(&mut self, offsets: &[i32]) {
+        self.profile = TunnelProfile {
+            cells: offsets.chunks_exact(2).map(|c| (c[0], c[1])).collect(),
+        };
+    }
+
+    /// `Direction` ordinal the tunnel is dug towards.
+    #[xross_method(critical)]
+    pub fn set_direction(&mut self, ordinal: i32) {
+        if let Some(dir) = Direction::from_ordinal(ordinal) {
+            self.direction = dir;
+        }
+    }
+
+    #[xross_method]
+    pub fn set_fluid_ids(&mut self, ids: &[i32]) {
+        self.rules.fluids = ids.iter().copied().collect();
+    }
+
+    #[xross_method]
+    pub fn set_falling_ids(&mut self, ids: &[i32]) {
+        self.rules.falling = ids.iter().copied().collect();
+    }
+
+    /// Returns the number of blocks to break.
+    #[xross_method(panicable)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan(
+        &mut self,
+        feet_x: i32,
+        feet_y: i32,
+        feet_z: i32,
+        eye_x: f64,
+        eye_y: f64,
+        eye_z: f64,
+        reach: f64,
+        length: i32,
+    ) -> usize {
+        let view = WorldSnapshot::instance().view();
+        let planned = plan_tunnel(
+            &view,
+            &self.rules,
+            &self.profile,
+            BlockPos::new(feet_x, feet_y, feet_z),
+            DVec3::new(eye_x, eye_y, eye_z),
+            self.direction,
+            reach,
+            length,
+        );
+
+        self.positions.clear();
+        self.rotations.clear();
+        for p in &planned {
+            self.positions.push(p.pos.pack());
+            self.rotations
+                .extend_from_slice(&[p.yaw, p.pitch, f32::from_bits(p.flags)]);
+        }
+        planned.len()
+    }
+
+    /// Break order packed like `BlockPos::asLong`.
+    #[xross_method(critical)]
+    pub fn get_positions_ptr(&self) -> *const u64 {
+        self.positions.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_positions_size(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_rotations_ptr(&self) -> *const f32 {
+        self.rotations.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_rotations_size(&self) -> usize {
+        self.rotations.len()
+    }
+}
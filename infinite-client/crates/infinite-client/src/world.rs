@@ -0,0 +1,14 @@
+pub mod clusters;
+pub mod events;
+pub mod pathfinding;
+pub mod raycast;
+pub mod snapshot;
+pub mod vein;
+
+use crate::graphics3d::mesh::BlockPos;
+
+/// Read access to voxel data shared by the native world algorithms.
+pub trait BlockSource {
+    /// Whether the block at `pos` should stop a ray or count as occupied.
+    fn is_solid(&self, pos: BlockPos) -> bool;
+}
@@ -0,0 +1,210 @@
+use super::snapshot::{WorldSnapshot, WorldView};
+use super::vein::Connectivity;
+use crate::graphics3d::mesh::{BlockPos, ChunkPos};
+use crate::infinite::feature::local::level::block_highlight::BlockHighlightFeature;
+use glam::DVec3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use xross_core::{XrossClass, xross_methods};
+
+/// Number of f64 values written per cluster:
+/// `id, count, centroid_x, centroid_y, centroid_z,
+///  min_x, min_y, min_z, max_x, max_y, max_z, distance`.
+pub const CLUSTER_STRIDE: usize = 12;
+/// Number of i32 values written per id total: `id, block_count, cluster_count`.
+pub const TOTAL_STRIDE: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct OreCluster {
+    pub id: i32,
+    pub count: usize,
+    pub centroid: DVec3,
+    pub min: BlockPos,
+    pub max: BlockPos,
+    pub distance: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OreTotal {
+    pub id: i32,
+    pub blocks: usize,
+    pub clusters: usize,
+}
+
+/// Groups target blocks within `radius` chunks of `player` into connected clusters
+/// of the same id. Clusters are sorted by distance from `player` to their centroid.
+pub fn analyze_clusters(
+    view: &WorldView,
+    targets: &FxHashSet<i32>,
+    player: DVec3,
+    radius: i32,
+    connectivity: Connectivity,
+) -> Vec<OreCluster> {
+    let center = ChunkPos::from_block_coords(player.x.floor() as i32, player.z.floor() as i32);
+    let mut blocks: FxHashMap<BlockPos, i32> = FxHashMap::default();
+    for (pos, section) in view.sections() {
+        if pos.chunk_pos().chessboard_distance(&center) > radius {
+            continue;
+        }
+        for (index, &id) in section.states().iter().enumerate() {
+            if targets.contains(&id) {
+                blocks.insert(pos.block_at(index), id);
+            }
+        }
+    }
+
+    let offsets = connectivity.offsets();
+    let mut visited = FxHashSet::default();
+    let mut clusters = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for (&start, &id) in &blocks {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut sum = DVec3::ZERO;
+        let mut count = 0;
+        let (mut min, mut max) = (start, start);
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            sum += DVec3::new(pos.x as f64, pos.y as f64, pos.z as f64);
+            count += 1;
+            min = BlockPos::new(min.x.min(pos.x), min.y.min(pos.y), min.z.min(pos.z));
+            max = BlockPos::new(max.x.max(pos.x), max.y.max(pos.y), max.z.max(pos.z));
+
+            for &(dx, dy, dz) in &offsets {
+                let next = pos.offset(dx, dy, dz);
+                if blocks.get(&next) == Some(&id) && visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let centroid = sum / count as f64 + DVec3::splat(0.5);
+        clusters.push(OreCluster {
+            id,
+            count,
+            centroid,
+            min,
+            max,
+            distance: centroid.distance(player),
+        });
+    }
+
+    clusters.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    clusters
+}
+
+pub fn cluster_totals(clusters: &[OreCluster]) -> Vec<OreTotal> {
+    let mut totals: FxHashMap<i32, OreTotal> = FxHashMap::default();
+    for cluster in clusters {
+        let total = totals.entry(cluster.id).or_insert(OreTotal {
+            id: cluster.id,
+            ..Default::default()
+        });
+        total.blocks += cluster.count;
+        total.clusters += 1;
+    }
+    let mut totals: Vec<OreTotal> = totals.into_values().collect();
+    totals.sort_by_key(|t| t.id);
+    totals
+}
+
+#[derive(XrossClass, Default)]
+pub struct OreClusterAnalyzer {
+    connectivity: Connectivity,
+    clusters: Vec<OreCluster>,
+    cluster_buffer: Vec<f64>,
+    total_buffer: Vec<i32>,
+}
+
+#[xross_methods]
+impl OreClusterAnalyzer {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `6`, `18` or `26` neighbors.
+    #[xross_method(critical)]
+    pub fn set_connectivity(&mut self, neighbors: i32) {
+        self.connectivity = Connectivity::from_neighbors(neighbors);
+    }
+
+    /// Returns the number of clusters found within `radius` chunks.
+    /// Targets are the blocks currently selected in the block highlight settings.
+    #[xross_method(panicable)]
+    pub fn analyze(&mut self, player_x: f64, player_y: f64, player_z: f64, radius: i32) -> usize {
+        let targets: FxHashSet<i32> = BlockHighlightFeature::targets().into_keys().collect();
+        let view = WorldSnapshot::instance().view();
+        self.clusters = analyze_clusters(
+            &view,
+            &targets,
+            DVec3::new(player_x, player_y, player_z),
+            radius.max(0),
+            self.connectivity,
+        );
+
+        self.cluster_buffer.clear();
+        for c in &self.clusters {
+            self.cluster_buffer.extend_from_slice(&[
+                c.id as f64,
+                c.count as f64,
+                c.centroid.x,
+                c.centroid.y,
+                c.centroid.z,
+                c.min.x as f64,
+                c.min.y as f64,
+                c.min.z as f64,
+                c.max.x as f64,
+                c.max.y as f64,
+                c.max.z as f64,
+                c.distance,
+            ]);
+        }
+
+        self.total_buffer.clear();
+        for t in cluster_totals(&self.clusters) {
+            self.total_buffer
+                .extend_from_slice(&[t.id, t.blocks as i32, t.clusters as i32]);
+        }
+        self.clusters.len()
+    }
+
+    /// Index of the nearest cluster of `id`, or `-1`. Clusters are sorted by distance,
+    /// so this is the first match.
+    #[xross_method(critical)]
+    pub fn nearest_cluster(&self, id: i32) -> i32 {
+        self.clusters
+            .iter()
+            .position(|c| c.id == id)
+            .map_or(-1, |i| i as i32)
+    }
+
+    #[xross_method(critical)]
+    pub fn get_cluster_ptr(&self) -> *const f64 {
+        self.cluster_buffer.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_cluster_size(&self) -> usize {
+        self.cluster_buffer.len()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_total_ptr(&self) -> *const i32 {
+        self.total_buffer.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_total_size(&self) -> usize {
+        self.total_buffer.len()
+    }
+}
+
+impl OreClusterAnalyzer {
+    pub fn clusters(&self) -> &[OreCluster] {
+        &self.clusters
+    }
+}
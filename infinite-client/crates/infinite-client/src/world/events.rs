@@ -0,0 +1,324 @@
+use super::snapshot::{SECTION_VOLUME, Section, WorldSnapshot, relative_index};
+use crate::graphics3d::mesh::{BlockMeshGenerator, BlockPos, ChunkPos, SectionPos};
+use crate::infinite::feature::local::level::block_highlight::BlockHighlightFeature;
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use xross_core::{XrossClass, xross_methods};
+
+/// Pending changes for one section. Later events overwrite earlier ones, so a
+/// burst of updates to the same section is applied and re-scanned only once.
+#[derive(Default)]
+struct PendingSection {
+    full: Option<Section>,
+    updates: FxHashMap<usize, i32>,
+}
+
+impl PendingSection {
+    /// New chunk data supersedes every update queued before it.
+    fn set_full(&mut self, section: Section) {
+        self.full = Some(section);
+        self.updates.clear();
+    }
+
+    fn set_state(&mut self, index: usize, id: i32) {
+        match &mut self.full {
+            Some(section) => section.set_state(index, id),
+            None => {
+                self.updates.insert(index, id);
+            }
+        }
+    }
+}
+
+/// Native replacement for draining `LevelManager.queue` on the JVM.
+/// Chunk data and block/delta updates are pushed in batches, coalesced per
+/// section, written to [`WorldSnapshot`] and re-scanned within a time budget.
+#[derive(XrossClass)]
+pub struct LevelEventQueue {
+    pending: FxHashMap<SectionPos, PendingSection>,
+    order: VecDeque<SectionPos>,
+    /// Highlight targets the current scan results were computed with.
+    targets: FxHashMap<i32, i32>,
+    max_y: i32,
+    max_sky_light: i32,
+    scanned: FxHashMap<SectionPos, Vec<(BlockPos, i32)>>,
+    changed: Vec<u64>,
+    dropped: Vec<u64>,
+}
+
+impl Default for LevelEventQueue {
+    fn default() -> Self {
+        Self {
+            pending: FxHashMap::default(),
+            order: VecDeque::new(),
+            targets: FxHashMap::default(),
+            max_y: i32::MAX,
+            max_sky_light: 15,
+            scanned: FxHashMap::default(),
+            changed: Vec::new(),
+            dropped: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl LevelEventQueue {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[xross_method(critical)]
+    pub fn set_max_y(&mut self, max_y: i32) {
+        self.max_y = max_y;
+    }
+
+    /// Air targets brighter than this sky light are ignored, matching the cave scan.
+    #[xross_method(critical)]
+    pub fn set_max_sky_light(&mut self, max_sky_light: i32) {
+        self.max_sky_light = max_sky_light;
+    }
+
+    /// `ids`, `sky_lights` and `block_lights` hold consecutive 4096-entry sections
+    /// starting at `min_section_y`.
+    #[xross_method]
+    pub fn push_chunk(
+        &mut self,
+        x: i32,
+        z: i32,
+        min_section_y: i32,
+        ids: &[i32],
+        sky_lights: &[i32],
+        block_lights: &[i32],
+    ) {
+        for (i, states) in ids.chunks_exact(SECTION_VOLUME).enumerate() {
+            let pos = SectionPos::new(x, min_section_y + i as i32, z);
+            let section = Section::new(
+                states,
+                section_slice(sky_lights, i),
+                section_slice(block_lights, i),
+            );
+            self.entry(pos).set_full(section);
+        }
+    }
+
+    #[xross_method]
+    pub fn push_chunk_unload(&mut self, x: i32, z: i32) {
+        let chunk = ChunkPos::new(x, z);
+        self.pending.retain(|pos, _| pos.chunk_pos() != chunk);
+        self.order.retain(|pos| pos.chunk_pos() != chunk);
+        WorldSnapshot::instance().remove_chunk(chunk);
+
+        let changed = &mut self.changed;
+        self.scanned.retain(|pos, _| {
+            let keep = pos.chunk_pos() != chunk;
+            if !keep {
+                changed.push(pos.pack());
+            }
+            keep
+        });
+    }
+
+    /// `positions` are packed like `BlockPos::asLong`.
+    #[xross_method]
+    pub fn push_block_updates(&mut self, positions: &[u64], states: &[i32]) {
+        for (&packed, &id) in positions.iter().zip(states) {
+            let pos = BlockPos::unpack(packed);
+            self.entry(pos.section_pos())
+                .set_state(SectionPos::local_index(pos), id);
+        }
+    }
+
+    /// `positions` use the section-relative short packing of `ClientboundSectionBlocksUpdatePacket`.
+    #[xross_method]
+    pub fn push_section_update(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        positions: &[i16],
+        states: &[i32],
+    ) {
+        let pending = self.entry(SectionPos::new(x, y, z));
+        for (&packed, &id) in positions.iter().zip(states) {
+            pending.set_state(relative_index(packed), id);
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn pending_count(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Applies queued sections to the world store and re-scans them until
+    /// `budget_us` microseconds have passed. At least one section is processed per
+    /// call so the queue always makes progress. Returns the number of sections processed.
+    /// Targets come from `BlockHighlightFeature`; when they change, every loaded
+    /// section is re-queued.
+    #[xross_method(panicable)]
+    pub fn process(&mut self, budget_us: u64) -> usize {
+        let start = Instant::now();
+        let budget = Duration::from_micros(budget_us);
+        let world = WorldSnapshot::instance();
+        let mut processed = 0;
+
+        let targets = BlockHighlightFeature::targets();
+        if targets != self.targets {
+            self.targets = targets;
+            self.requeue_all();
+        }
+
+        while let Some(pos) = self.order.pop_front() {
+            if processed > 0 && start.elapsed() >= budget {
+                self.order.push_front(pos);
+                break;
+            }
+            if let Some(pending) = self.pending.remove(&pos) {
+                if let Some(section) = pending.full {
+                    world.insert_section(pos, section);
+                }
+                if !pending.updates.is_empty() {
+                    let loaded = world.modify_section(pos, |section| {
+                        for (&index, &id) in &pending.updates {
+                            section.set_state(index, id);
+                        }
+                    });
+                    if !loaded {
+                        self.dropped.push(pos.pack());
+                    }
+                }
+            }
+            self.rescan(pos);
+            processed += 1;
+        }
+        processed
+    }
+
+    /// Packed [`SectionPos`] values whose scan result changed since the last
+    /// [`Self::clear_changed`].
+    #[xross_method(critical)]
+    pub fn get_changed_ptr(&self) -> *const u64 {
+        self.changed.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_changed_size(&self) -> usize {
+        self.changed.len()
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_changed(&mut self) {
+        self.changed.clear();
+    }
+
+    /// Packed [`SectionPos`] values whose block updates were discarded because the
+    /// section was not loaded yet, e.g. an update that arrived before its chunk data.
+    /// The section has to be re-sent to pick them up.
+    #[xross_method(critical)]
+    pub fn get_dropped_ptr(&self) -> *const u64 {
+        self.dropped.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_dropped_size(&self) -> usize {
+        self.dropped.len()
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_dropped(&mut self) {
+        self.dropped.clear();
+    }
+
+    /// Adds the scanned blocks of one section to `generator`.
+    #[xross_method]
+    pub fn add_section_to(&self, generator: &mut BlockMeshGenerator, x: i32, y: i32, z: i32) {
+        if let Some(blocks) = self.scanned.get(&SectionPos::new(x, y, z)) {
+            for (pos, color) in blocks {
+                generator.add_block(pos.x, pos.y, pos.z, *color);
+            }
+        }
+    }
+
+    #[xross_method(critical)]
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.order.clear();
+        self.scanned.clear();
+        self.changed.clear();
+        self.dropped.clear();
+    }
+}
+
+impl LevelEventQueue {
+    pub fn scanned(&self) -> &FxHashMap<SectionPos, Vec<(BlockPos, i32)>> {
+        &self.scanned
+    }
+
+    fn entry(&mut self, pos: SectionPos) -> &mut PendingSection {
+        let order = &mut self.order;
+        self.pending.entry(pos).or_insert_with(|| {
+            order.push_back(pos);
+            PendingSection::default()
+        })
+    }
+
+    fn requeue_all(&mut self) {
+        let loaded: Vec<SectionPos> = WorldSnapshot::instance()
+            .view()
+            .sections()
+            .map(|(pos, _)| *pos)
+            .collect();
+        for pos in loaded {
+            self.entry(pos);
+        }
+    }
+
+    fn rescan(&mut self, pos: SectionPos) {
+        let blocks = self.scan(pos);
+        let previous = if blocks.is_empty() {
+            self.scanned.remove(&pos)
+        } else {
+            self.scanned.insert(pos, blocks)
+        };
+        let changed = match (&previous, self.scanned.get(&pos)) {
+            (None, None) => false,
+            (Some(old), Some(new)) => old != new,
+            _ => true,
+        };
+        if changed {
+            self.changed.push(pos.pack());
+        }
+    }
+
+    fn scan(&self, pos: SectionPos) -> Vec<(BlockPos, i32)> {
+        let mut blocks = Vec::new();
+        if self.targets.is_empty() || pos.origin().y > self.max_y {
+            return blocks;
+        }
+        let view = WorldSnapshot::instance().view();
+        let Some(section) = view.section(pos) else {
+            return blocks;
+        };
+
+        for (index, &id) in section.states().iter().enumerate() {
+            let Some(&color) = self.targets.get(&id) else {
+                continue;
+            };
+            let block = pos.block_at(index);
+            if block.y > self.max_y {
+                continue;
+            }
+            if view.is_air_id(id) && section.sky_light(index) as i32 > self.max_sky_light {
+                continue;
+            }
+            blocks.push((block, color));
+        }
+        blocks
+    }
+}
+
+fn section_slice(data: &[i32], i: usize) -> &[i32] {
+    data.get(i * SECTION_VOLUME..(i + 1) * SECTION_VOLUME)
+        .unwrap_or(&[])
+}
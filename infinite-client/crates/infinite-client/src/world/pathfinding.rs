@@ -0,0 +1,456 @@
+use super::snapshot::{WorldSnapshot, WorldView};
+use crate::graphics3d::mesh::{BlockPos, InfiniteMesh, Line};
+use glam::DVec3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use xross_core::{XrossClass, xross_methods};
+
+const WALK_COST: f64 = 1.0;
+const DIAGONAL_COST: f64 = std::f64::consts::SQRT_2;
+const JUMP_COST: f64 = 2.0;
+const CLIMB_COST: f64 = 1.5;
+
+/// Goal sets up to this size use the exact distance to the nearest goal as heuristic;
+/// larger ones use the distance to their bounding box so each node stays O(1).
+const EXACT_GOAL_LIMIT: usize = 16;
+
+const HORIZONTAL: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const DIAGONAL: [(i32, i32); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+#[derive(Clone, Debug)]
+pub enum PathGoal {
+    Block(BlockPos),
+    /// Inclusive box of feet positions.
+    Region {
+        min: BlockPos,
+        max: BlockPos,
+    },
+    /// Built with [`PathGoal::any_of`], which precomputes the bounding box.
+    AnyOf {
+        positions: FxHashSet<BlockPos>,
+        min: BlockPos,
+        max: BlockPos,
+    },
+}
+
+impl PathGoal {
+    pub fn any_of(positions: FxHashSet<BlockPos>) -> Self {
+        let mut iter = positions.iter();
+        let first = iter.next().copied().unwrap_or_default();
+        let (min, max) = iter.fold((first, first), |(min, max), p| {
+            (
+                BlockPos::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                BlockPos::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        PathGoal::AnyOf {
+            positions,
+            min,
+            max,
+        }
+    }
+
+    pub fn is_reached(&self, pos: BlockPos) -> bool {
+        match self {
+            PathGoal::Block(goal) => *goal == pos,
+            PathGoal::Region { min, max } => {
+                (min.x..=max.x).contains(&pos.x)
+                    && (min.y..=max.y).contains(&pos.y)
+                    && (min.z..=max.z).contains(&pos.z)
+            }
+            PathGoal::AnyOf { positions, .. } => positions.contains(&pos),
+        }
+    }
+
+    /// Straight-line distance, never larger than the real move cost.
+    pub fn heuristic(&self, pos: BlockPos) -> f64 {
+        let p = to_vec(pos);
+        match self {
+            PathGoal::Block(goal) => p.distance(to_vec(*goal)),
+            PathGoal::Region { min, max } => {
+                let clamped = p.clamp(to_vec(*min), to_vec(*max));
+                p.distance(clamped)
+            }
+            PathGoal::AnyOf { positions, .. } if positions.len() <= EXACT_GOAL_LIMIT => positions
+                .iter()
+                .map(|goal| p.distance(to_vec(*goal)))
+                .fold(f64::INFINITY, f64::min),
+            // Every goal lies inside the box, so this never overestimates.
+            PathGoal::AnyOf { min, max, .. } => {
+                let clamped = p.clamp(to_vec(*min), to_vec(*max));
+                p.distance(clamped)
+            }
+        }
+    }
+}
+
+/// Movement rules. Blocks are classified by state id: `passable` blocks can be
+/// walked through, `climbable` ones (ladders, vines) can also be climbed, and
+/// `avoid` ones (lava, fire, cactus...) are never entered or stood on.
+#[derive(Clone, Debug)]
+pub struct MovementRules {
+    pub passable: FxHashSet<i32>,
+    pub climbable: FxHashSet<i32>,
+    pub avoid: FxHashSet<i32>,
+    pub max_fall: i32,
+    pub allow_diagonal: bool,
+}
+
+impl Default for MovementRules {
+    fn default() -> Self {
+        Self {
+            passable: FxHashSet::default(),
+            climbable: FxHashSet::default(),
+            avoid: FxHashSet::default(),
+            max_fall: 3,
+            allow_diagonal: true,
+        }
+    }
+}
+
+struct Walker<'a> {
+    view: &'a WorldView<'a>,
+    rules: &'a MovementRules,
+}
+
+impl Walker<'_> {
+    /// Unloaded blocks are neither passable nor supporting, so paths stay inside loaded terrain.
+    fn id(&self, pos: BlockPos) -> Option<i32> {
+        self.view.block(pos)
+    }
+
+    fn is_passable(&self, pos: BlockPos) -> bool {
+        self.id(pos).is_some_and(|id| {
+            !self.rules.avoid.contains(&id)
+                && (self.rules.passable.contains(&id)
+                    || self.rules.climbable.contains(&id)
+                    || self.view.is_air_id(id))
+        })
+    }
+
+    fn is_climbable(&self, pos: BlockPos) -> bool {
+        self.id(pos)
+            .is_some_and(|id| self.rules.climbable.contains(&id))
+    }
+
+    fn is_support(&self, pos: BlockPos) -> bool {
+        self.id(pos)
+            .is_some_and(|id| !self.rules.avoid.contains(&id) && !self.is_passable(pos))
+            || self.is_climbable(pos)
+    }
+
+    fn has_room(&self, feet: BlockPos) -> bool {
+        self.is_passable(feet) && self.is_passable(feet.offset(0, 1, 0))
+    }
+
+    fn can_stand(&self, feet: BlockPos) -> bool {
+        self.has_room(feet) && (self.is_support(feet.offset(0, -1, 0)) || self.is_climbable(feet))
+    }
+
+    fn neighbors(&self, pos: BlockPos, out: &mut Vec<(BlockPos, f64)>) {
+        out.clear();
+        for (dx, dz) in HORIZONTAL {
+            let next = pos.offset(dx, 0, dz);
+            if self.can_stand(next) {
+                out.push((next, WALK_COST));
+                continue;
+            }
+
+            let up = next.offset(0, 1, 0);
+            if self.can_stand(up) && self.is_passable(pos.offset(0, 2, 0)) {
+                out.push((up, JUMP_COST));
+                continue;
+            }
+
+            if self.has_room(next) {
+                let mut below = next;
+                for drop in 1..=self.rules.max_fall {
+                    below = below.offset(0, -1, 0);
+                    if !self.is_passable(below) {
+                        break;
+                    }
+                    if self.can_stand(below) {
+                        out.push((below, WALK_COST + drop as f64));
+                        break;
+                    }
+                }
+            }
+        }
+
+        if self.rules.allow_diagonal {
+            for (dx, dz) in DIAGONAL {
+                let next = pos.offset(dx, 0, dz);
+                if self.can_stand(next)
+                    && self.has_room(pos.offset(dx, 0, 0))
+                    && self.has_room(pos.offset(0, 0, dz))
+                {
+                    out.push((next, DIAGONAL_COST));
+                }
+            }
+        }
+
+        if self.is_climbable(pos) {
+            let up = pos.offset(0, 1, 0);
+            if self.has_room(up) && (self.is_climbable(up) || self.can_stand(up)) {
+                out.push((up, CLIMB_COST));
+            }
+        }
+        let down = pos.offset(0, -1, 0);
+        if self.is_climbable(down) && self.has_room(down) {
+            out.push((down, CLIMB_COST));
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OpenNode {
+    pos: BlockPos,
+    f: f64,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.f == other.f
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    // BinaryHeap is a max-heap, so the comparison is reversed.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f)
+    }
+}
+
+/// A* search over walkable voxels of the [`WorldSnapshot`].
+/// Returns the waypoints from `start` to the goal (both inclusive) and whether the
+/// goal was reached. When it is not, the path leads to the explored node closest to it.
+pub fn find_path(
+    view: &WorldView,
+    rules: &MovementRules,
+    start: BlockPos,
+    goal: &PathGoal,
+    max_nodes: usize,
+) -> (Vec<BlockPos>, bool) {
+    let walker = Walker { view, rules };
+    let mut open = BinaryHeap::new();
+    let mut came_from: FxHashMap<BlockPos, BlockPos> = FxHashMap::default();
+    let mut g_score: FxHashMap<BlockPos, f64> = FxHashMap::default();
+    let mut closed: FxHashSet<BlockPos> = FxHashSet::default();
+    let mut neighbors = Vec::with_capacity(12);
+
+    g_score.insert(start, 0.0);
+    open.push(OpenNode {
+        pos: start,
+        f: goal.heuristic(start),
+    });
+    let mut best = (start, goal.heuristic(start));
+
+    while let Some(OpenNode { pos, .. }) = open.pop() {
+        if goal.is_reached(pos) {
+            return (reconstruct(&came_from, pos), true);
+        }
+        if !closed.insert(pos) {
+            continue;
+        }
+        if closed.len() > max_nodes {
+            break;
+        }
+
+        let g = g_score[&pos];
+        walker.neighbors(pos, &mut neighbors);
+        for &(next, cost) in &neighbors {
+            if closed.contains(&next) {
+                continue;
+            }
+            let tentative = g + cost;
+            if g_score.get(&next).is_none_or(|&old| tentative < old) {
+                g_score.insert(next, tentative);
+                came_from.insert(next, pos);
+                let h = goal.heuristic(next);
+                if h < best.1 {
+                    best = (next, h);
+                }
+                open.push(OpenNode {
+                    pos: next,
+                    f: tentative + h,
+                });
+            }
+        }
+    }
+    (reconstruct(&came_from, best.0), false)
+}
+
+fn reconstruct(came_from: &FxHashMap<BlockPos, BlockPos>, end: BlockPos) -> Vec<BlockPos> {
+    let mut path = vec![end];
+    let mut current = end;
+    while let Some(&prev) = came_from.get(&current) {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+fn to_vec(pos: BlockPos) -> DVec3 {
+    DVec3::new(pos.x as f64, pos.y as f64, pos.z as f64)
+}
+
+#[derive(XrossClass)]
+pub struct PathFinder {
+    rules: MovementRules,
+    goal: Option<PathGoal>,
+    max_nodes: usize,
+    path: Vec<u64>,
+    reached: bool,
+}
+
+impl Default for PathFinder {
+    fn default() -> Self {
+        Self {
+            rules: MovementRules::default(),
+            goal: None,
+            max_nodes: 20_000,
+            path: Vec::new(),
+            reached: false,
+        }
+    }
+}
+
+#[xross_methods]
+impl PathFinder {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ids that can be walked through in addition to the world's air ids.
+    #[xross_method]
+    pub fn set_passable_ids(&mut self, ids: &[i32]) {
+        self.rules.passable = ids.iter().copied().collect();
+    }
+
+    #[xross_method]
+    pub fn set_climbable_ids(&mut self, ids: &[i32]) {
+        self.rules.climbable = ids.iter().copied().collect();
+    }
+
+    #[xross_method]
+    pub fn set_avoid_ids(&mut self, ids: &[i32]) {
+        self.rules.avoid = ids.iter().copied().collect();
+    }
+
+    #[xross_method(critical)]
+    pub fn set_max_fall(&mut self, blocks: i32) {
+        self.rules.max_fall = blocks.max(0);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_allow_diagonal(&mut self, allow: bool) {
+        self.rules.allow_diagonal = allow;
+    }
+
+    #[xross_method(critical)]
+    pub fn set_max_nodes(&mut self, max_nodes: i32) {
+        self.max_nodes = max_nodes.max(1) as usize;
+    }
+
+    #[xross_method(critical)]
+    pub fn set_goal_block(&mut self, x: i32, y: i32, z: i32) {
+        self.goal = Some(PathGoal::Block(BlockPos::new(x, y, z)));
+    }
+
+    #[xross_method(critical)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_goal_region(
+        &mut self,
+        min_x: i32,
+        min_y: i32,
+        min_z: i32,
+        max_x: i32,
+        max_y: i32,
+        max_z: i32,
+    ) {
+        self.goal = Some(PathGoal::Region {
+            min: BlockPos::new(min_x.min(max_x), min_y.min(max_y), min_z.min(max_z)),
+            max: BlockPos::new(min_x.max(max_x), min_y.max(max_y), min_z.max(max_z)),
+        });
+    }
+
+    /// `positions` are packed like `BlockPos::asLong`.
+    #[xross_method]
+    pub fn set_goal_any(&mut self, positions: &[u64]) {
+        self.goal = Some(PathGoal::any_of(
+            positions.iter().map(|&p| BlockPos::unpack(p)).collect(),
+        ));
+    }
+
+    /// Searches from the feet position `start`. Returns `true` if the goal was reached;
+    /// otherwise the stored path leads as close to the goal as the search got.
+    #[xross_method(panicable)]
+    pub fn find(&mut self, x: i32, y: i32, z: i32) -> bool {
+        self.path.clear();
+        self.reached = false;
+        let Some(goal) = &self.goal else {
+            return false;
+        };
+
+        let view = WorldSnapshot::instance().view();
+        let (path, reached) = find_path(
+            &view,
+            &self.rules,
+            BlockPos::new(x, y, z),
+            goal,
+            self.max_nodes,
+        );
+        self.path = path.iter().map(|p| p.pack()).collect();
+        self.reached = reached;
+        reached
+    }
+
+    #[xross_method(critical)]
+    pub fn is_reached(&self) -> bool {
+        self.reached
+    }
+
+    /// Waypoints packed like `BlockPos::asLong`.
+    #[xross_method(critical)]
+    pub fn get_path_ptr(&self) -> *const u64 {
+        self.path.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_path_size(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Preview of the path as lines through the bottom center of each waypoint.
+    #[xross_method]
+    pub fn build_mesh(&self, mesh: &mut InfiniteMesh, color: i32) {
+        let center = |packed: u64| {
+            let pos = BlockPos::unpack(packed);
+            DVec3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.05, pos.z as f64 + 0.5)
+        };
+        for pair in self.path.windows(2) {
+            mesh.push_line(&Line {
+                start: center(pair[0]),
+                end: center(pair[1]),
+                color,
+            });
+        }
+    }
+}
+
+impl PathFinder {
+    pub fn path(&self) -> impl Iterator<Item = BlockPos> + '_ {
+        self.path.iter().map(|&p| BlockPos::unpack(p))
+    }
+}
@@ -0,0 +1,183 @@
+use super::BlockSource;
+use crate::graphics3d::mesh::{Axis, BlockPos, Direction};
+use glam::DVec3;
+use xross_core::{XrossClass, xross_methods};
+
+/// Longest distance traced. The client never has blocks loaded more than 32 chunks
+/// (512 blocks) away, and callers hold the snapshot read lock for the whole walk.
+pub const MAX_RAY_DISTANCE: f64 = 1024.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockHit {
+    pub pos: BlockPos,
+    pub face: Direction,
+    pub distance: f64,
+}
+
+/// Result of a raycast for the JVM side. `face` is the `Direction` ordinal.
+#[derive(XrossClass, Default, Clone, Debug)]
+#[xross(clonable(critical), drop(critical))]
+pub struct RaycastHit {
+    #[xross_field]
+    pub hit: bool,
+    #[xross_field]
+    pub x: i32,
+    #[xross_field]
+    pub y: i32,
+    #[xross_field]
+    pub z: i32,
+    #[xross_field]
+    pub face: i32,
+    #[xross_field]
+    pub distance: f64,
+}
+
+#[xross_methods]
+impl RaycastHit {
+    #[xross_new(critical)]
+    pub fn miss() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Option<BlockHit>> for RaycastHit {
+    fn from(hit: Option<BlockHit>) -> Self {
+        match hit {
+            Some(hit) => Self {
+                hit: true,
+                x: hit.pos.x,
+                y: hit.pos.y,
+                z: hit.pos.z,
+                face: hit.face.ordinal(),
+                distance: hit.distance,
+            },
+            None => Self::miss(),
+        }
+    }
+}
+
+/// Amanatides–Woo voxel traversal. Returns the first solid block along the ray
+/// together with the face it was entered through. `max_distance` is clamped to
+/// [`MAX_RAY_DISTANCE`].
+pub fn raycast<S: BlockSource + ?Sized>(
+    source: &S,
+    origin: DVec3,
+    direction: DVec3,
+    max_distance: f64,
+) -> Option<BlockHit> {
+    // The DDA loop only stops once the distance passes `max_distance`, so NaN or infinite
+    // limits would never terminate through empty or unloaded space.
+    if !max_distance.is_finite() || max_distance < 0.0 || !origin.is_finite() {
+        return None;
+    }
+    let max_distance = max_distance.min(MAX_RAY_DISTANCE);
+    // `try_normalize` rejects zero and non-finite directions.
+    let dir = direction.try_normalize()?;
+    let mut pos = BlockPos::new(
+        origin.x.floor() as i32,
+        origin.y.floor() as i32,
+        origin.z.floor() as i32,
+    );
+
+    let step = |d: f64| if d > 0.0 { 1 } else { -1 };
+    let (step_x, step_y, step_z) = (step(dir.x), step(dir.y), step(dir.z));
+
+    // Distance along the ray to the first boundary on each axis, and between boundaries.
+    let boundary = |o: f64, cell: i32, d: f64| {
+        if d == 0.0 {
+            f64::INFINITY
+        } else if d > 0.0 {
+            ((cell + 1) as f64 - o) / d
+        } else {
+            (cell as f64 - o) / d
+        }
+    };
+    let delta = |d: f64| {
+        if d == 0.0 {
+            f64::INFINITY
+        } else {
+            (1.0 / d).abs()
+        }
+    };
+    let mut t_max = DVec3::new(
+        boundary(origin.x, pos.x, dir.x),
+        boundary(origin.y, pos.y, dir.y),
+        boundary(origin.z, pos.z, dir.z),
+    );
+    let t_delta = DVec3::new(delta(dir.x), delta(dir.y), delta(dir.z));
+
+    if source.is_solid(pos) {
+        // Started inside a block: report the face the ray leaves through.
+        let axis = dominant_axis(dir);
+        return Some(BlockHit {
+            pos,
+            face: face_for(axis, dir).opposite(),
+            distance: 0.0,
+        });
+    }
+
+    loop {
+        let axis = if t_max.x < t_max.y {
+            if t_max.x < t_max.z { Axis::X } else { Axis::Z }
+        } else if t_max.y < t_max.z {
+            Axis::Y
+        } else {
+            Axis::Z
+        };
+
+        let distance = match axis {
+            Axis::X => {
+                pos.x += step_x;
+                let t = t_max.x;
+                t_max.x += t_delta.x;
+                t
+            }
+            Axis::Y => {
+                pos.y += step_y;
+                let t = t_max.y;
+                t_max.y += t_delta.y;
+                t
+            }
+            Axis::Z => {
+                pos.z += step_z;
+                let t = t_max.z;
+                t_max.z += t_delta.z;
+                t
+            }
+        };
+
+        if distance > max_distance {
+            return None;
+        }
+        if source.is_solid(pos) {
+            return Some(BlockHit {
+                pos,
+                face: face_for(axis, dir),
+                distance,
+            });
+        }
+    }
+}
+
+fn dominant_axis(dir: DVec3) -> Axis {
+    let abs = dir.abs();
+    if abs.x >= abs.y && abs.x >= abs.z {
+        Axis::X
+    } else if abs.y >= abs.z {
+        Axis::Y
+    } else {
+        Axis::Z
+    }
+}
+
+/// The face hit when entering a voxel along `axis` while moving in `dir`.
+fn face_for(axis: Axis, dir: DVec3) -> Direction {
+    match axis {
+        Axis::X if dir.x > 0.0 => Direction::West,
+        Axis::X => Direction::East,
+        Axis::Y if dir.y > 0.0 => Direction::Down,
+        Axis::Y => Direction::Up,
+        Axis::Z if dir.z > 0.0 => Direction::North,
+        Axis::Z => Direction::South,
+    }
+}
@@ -0,0 +1,294 @@
+use super::BlockSource;
+use super::raycast::{RaycastHit, raycast};
+use crate::graphics3d::mesh::{BlockPos, ChunkPos, SectionPos};
+use crate::infinite::INFINITE_CLIENT;
+use glam::DVec3;
+use parking_lot::{RwLock, RwLockReadGuard};
+use rustc_hash::{FxHashMap, FxHashSet};
+use xross_core::{XrossClass, xross_methods};
+
+pub const SECTION_VOLUME: usize = 4096;
+/// Returned by id getters when the section is not loaded.
+pub const UNLOADED_ID: i32 = -1;
+
+/// Block state ids and light of one 16x16x16 section, indexed by
+/// [`SectionPos::local_index`].
+#[derive(Clone)]
+pub struct Section {
+    states: Box<[i32]>,
+    sky_light: Box<[u8]>,
+    block_light: Box<[u8]>,
+}
+
+impl Section {
+    /// Missing or short light arrays are padded with `0`.
+    pub fn new(states: &[i32], sky_light: &[i32], block_light: &[i32]) -> Self {
+        let mut section = Self::empty();
+        let n = states.len().min(SECTION_VOLUME);
+        section.states[..n].copy_from_slice(&states[..n]);
+        for (dst, &src) in section.sky_light.iter_mut().zip(sky_light) {
+            *dst = src.clamp(0, 15) as u8;
+        }
+        for (dst, &src) in section.block_light.iter_mut().zip(block_light) {
+            *dst = src.clamp(0, 15) as u8;
+        }
+        section
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            states: vec![0; SECTION_VOLUME].into_boxed_slice(),
+            sky_light: vec![0; SECTION_VOLUME].into_boxed_slice(),
+            block_light: vec![0; SECTION_VOLUME].into_boxed_slice(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn state(&self, index: usize) -> i32 {
+        self.states[index]
+    }
+
+    #[inline(always)]
+    pub fn set_state(&mut self, index: usize, id: i32) {
+        self.states[index] = id;
+    }
+
+    #[inline(always)]
+    pub fn sky_light(&self, index: usize) -> u8 {
+        self.sky_light[index]
+    }
+
+    #[inline(always)]
+    pub fn block_light(&self, index: usize) -> u8 {
+        self.block_light[index]
+    }
+
+    pub fn states(&self) -> &[i32] {
+        &self.states
+    }
+}
+
+/// Persistent copy of the loaded world, keyed by [`SectionPos`].
+/// Fed by chunk load/unload and block update events so native algorithms can
+/// query blocks without re-marshalling data from the JVM every tick.
+#[derive(XrossClass)]
+#[xross(unclonable)]
+#[xross_package("world")]
+pub struct WorldSnapshot {
+    sections: RwLock<FxHashMap<SectionPos, Section>>,
+    air_ids: RwLock<FxHashSet<i32>>,
+}
+
+impl Default for WorldSnapshot {
+    fn default() -> Self {
+        Self {
+            sections: Default::default(),
+            air_ids: RwLock::new(FxHashSet::from_iter([0])),
+        }
+    }
+}
+
+impl WorldSnapshot {
+    pub fn instance() -> &'static Self {
+        &INFINITE_CLIENT.world
+    }
+
+    /// Locks the store for reading. Prefer this for bulk queries instead of the
+    /// per-call getters, which take the lock every time.
+    pub fn view(&self) -> WorldView<'_> {
+        WorldView {
+            sections: self.sections.read(),
+            air_ids: self.air_ids.read(),
+        }
+    }
+
+    pub fn insert_section(&self, pos: SectionPos, section: Section) {
+        self.sections.write().insert(pos, section);
+    }
+
+    pub fn remove_chunk(&self, chunk: ChunkPos) {
+        self.sections
+            .write()
+            .retain(|pos, _| pos.x != chunk.x || pos.z != chunk.z);
+    }
+
+    /// Runs `f` on a loaded section. Returns `false` if the section is not loaded.
+    pub fn modify_section(&self, pos: SectionPos, f: impl FnOnce(&mut Section)) -> bool {
+        let mut sections = self.sections.write();
+        match sections.get_mut(&pos) {
+            Some(section) => {
+                f(section);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn update_block(&self, pos: BlockPos, id: i32) -> bool {
+        self.modify_section(pos.section_pos(), |section| {
+            section.set_state(SectionPos::local_index(pos), id)
+        })
+    }
+
+    /// `positions` use Minecraft's section-relative short packing
+    /// (`x << 8 | z << 4 | y`) as sent by `ClientboundSectionBlocksUpdatePacket`.
+    pub fn update_section(&self, pos: SectionPos, positions: &[i16], states: &[i32]) -> bool {
+        self.modify_section(pos, |section| {
+            for (&packed, &id) in positions.iter().zip(states) {
+                section.set_state(relative_index(packed), id);
+            }
+        })
+    }
+
+    pub fn is_air_id(&self, id: i32) -> bool {
+        self.air_ids.read().contains(&id)
+    }
+}
+
+#[xross_methods]
+impl WorldSnapshot {
+    #[xross_method]
+    pub fn load_section(
+        x: i32,
+        y: i32,
+        z: i32,
+        ids: &[i32],
+        sky_lights: &[i32],
+        block_lights: &[i32],
+    ) {
+        if ids.len() < SECTION_VOLUME {
+            return;
+        }
+        Self::instance().insert_section(
+            SectionPos::new(x, y, z),
+            Section::new(ids, sky_lights, block_lights),
+        );
+    }
+
+    #[xross_method]
+    pub fn unload_chunk(x: i32, z: i32) {
+        Self::instance().remove_chunk(ChunkPos::new(x, z));
+    }
+
+    #[xross_method]
+    pub fn set_block(x: i32, y: i32, z: i32, id: i32) {
+        Self::instance().update_block(BlockPos::new(x, y, z), id);
+    }
+
+    #[xross_method]
+    pub fn apply_section_delta(x: i32, y: i32, z: i32, positions: &[i16], states: &[i32]) {
+        Self::instance().update_section(SectionPos::new(x, y, z), positions, states);
+    }
+
+    /// Ids treated as empty space (air, cave_air, void_air).
+    #[xross_method]
+    pub fn set_air_ids(ids: &[i32]) {
+        *Self::instance().air_ids.write() = ids.iter().copied().collect();
+    }
+
+    #[xross_method]
+    pub fn clear() {
+        Self::instance().sections.write().clear();
+    }
+
+    #[xross_method(critical)]
+    pub fn section_count() -> usize {
+        Self::instance().sections.read().len()
+    }
+
+    #[xross_method]
+    pub fn get_block(x: i32, y: i32, z: i32) -> i32 {
+        Self::instance()
+            .view()
+            .block(BlockPos::new(x, y, z))
+            .unwrap_or(UNLOADED_ID)
+    }
+
+    #[xross_method]
+    pub fn get_sky_light(x: i32, y: i32, z: i32) -> i32 {
+        Self::instance()
+            .view()
+            .sky_light(BlockPos::new(x, y, z))
+            .map_or(0, i32::from)
+    }
+
+    #[xross_method]
+    #[allow(clippy::too_many_arguments)]
+    pub fn raycast(
+        origin_x: f64,
+        origin_y: f64,
+        origin_z: f64,
+        dir_x: f64,
+        dir_y: f64,
+        dir_z: f64,
+        max_distance: f64,
+    ) -> RaycastHit {
+        raycast(
+            &Self::instance().view(),
+            DVec3::new(origin_x, origin_y, origin_z),
+            DVec3::new(dir_x, dir_y, dir_z),
+            max_distance,
+        )
+        .into()
+    }
+}
+
+/// Read-locked view of a [`WorldSnapshot`].
+pub struct WorldView<'a> {
+    sections: RwLockReadGuard<'a, FxHashMap<SectionPos, Section>>,
+    air_ids: RwLockReadGuard<'a, FxHashSet<i32>>,
+}
+
+impl WorldView<'_> {
+    pub fn section(&self, pos: SectionPos) -> Option<&Section> {
+        self.sections.get(&pos)
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = (&SectionPos, &Section)> {
+        self.sections.iter()
+    }
+
+    pub fn block(&self, pos: BlockPos) -> Option<i32> {
+        self.section(pos.section_pos())
+            .map(|s| s.state(SectionPos::local_index(pos)))
+    }
+
+    pub fn sky_light(&self, pos: BlockPos) -> Option<u8> {
+        self.section(pos.section_pos())
+            .map(|s| s.sky_light(SectionPos::local_index(pos)))
+    }
+
+    pub fn block_light(&self, pos: BlockPos) -> Option<u8> {
+        self.section(pos.section_pos())
+            .map(|s| s.block_light(SectionPos::local_index(pos)))
+    }
+
+    pub fn is_loaded(&self, pos: BlockPos) -> bool {
+        self.sections.contains_key(&pos.section_pos())
+    }
+
+    pub fn is_air_id(&self, id: i32) -> bool {
+        self.air_ids.contains(&id)
+    }
+
+    /// Unloaded blocks count as air.
+    pub fn is_air(&self, pos: BlockPos) -> bool {
+        self.block(pos).is_none_or(|id| self.is_air_id(id))
+    }
+}
+
+impl BlockSource for WorldView<'_> {
+    fn is_solid(&self, pos: BlockPos) -> bool {
+        !self.is_air(pos)
+    }
+}
+
+/// Converts a `SectionPos.sectionRelativePos` short into a section array index.
+#[inline(always)]
+pub fn relative_index(packed: i16) -> usize {
+    let packed = packed as u16 as i32;
+    let x = (packed >> 8) & 15;
+    let z = (packed >> 4) & 15;
+    let y = packed & 15;
+    ((y << 8) | (z << 4) | x) as usize
+}
@@ -0,0 +1,201 @@
+use super::snapshot::{WorldSnapshot, WorldView};
+use crate::graphics3d::mesh::{BlockMesh, BlockMeshGenerator, BlockPos, InfiniteMesh};
+use glam::DVec3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use xross_core::{XrossClass, xross_methods};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Shared faces only.
+    #[default]
+    Faces,
+    /// Faces and edges.
+    Edges,
+    /// Faces, edges and corners.
+    Corners,
+}
+
+impl Connectivity {
+    pub fn from_neighbors(n: i32) -> Self {
+        match n {
+            18 => Connectivity::Edges,
+            26 => Connectivity::Corners,
+            _ => Connectivity::Faces,
+        }
+    }
+
+    pub fn offsets(&self) -> Vec<(i32, i32, i32)> {
+        let mut offsets = Vec::with_capacity(26);
+        for dy in -1..=1 {
+            for dz in -1..=1 {
+                for dx in -1..=1 {
+                    let axes = (dx != 0) as i32 + (dy != 0) as i32 + (dz != 0) as i32;
+                    let allowed = match self {
+                        Connectivity::Faces => axes == 1,
+                        Connectivity::Edges => axes == 1 || axes == 2,
+                        Connectivity::Corners => axes >= 1,
+                    };
+                    if allowed {
+                        offsets.push((dx, dy, dz));
+                    }
+                }
+            }
+        }
+        offsets
+    }
+}
+
+/// Flood fill from `start` over blocks matching its id (or its id group).
+/// The result is sorted by distance to `origin`, closest first.
+pub fn find_vein(
+    view: &WorldView,
+    start: BlockPos,
+    origin: DVec3,
+    connectivity: Connectivity,
+    max_blocks: usize,
+    groups: &FxHashMap<i32, usize>,
+) -> Vec<BlockPos> {
+    let Some(start_id) = view.block(start) else {
+        return Vec::new();
+    };
+    if view.is_air_id(start_id) {
+        return Vec::new();
+    }
+    let start_group = groups.get(&start_id).copied();
+    let matches = |id: i32| {
+        id == start_id || (start_group.is_some() && groups.get(&id).copied() == start_group)
+    };
+
+    let offsets = connectivity.offsets();
+    let mut visited = FxHashSet::default();
+    let mut queue = VecDeque::new();
+    let mut vein = Vec::new();
+    visited.insert(start);
+    queue.push_back(start);
+
+    while let Some(pos) = queue.pop_front() {
+        vein.push(pos);
+        if vein.len() >= max_blocks {
+            break;
+        }
+        for &(dx, dy, dz) in &offsets {
+            let next = pos.offset(dx, dy, dz);
+            if visited.contains(&next) {
+                continue;
+            }
+            if view.block(next).is_some_and(matches) {
+                visited.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let center = |p: &BlockPos| DVec3::new(p.x as f64 + 0.5, p.y as f64 + 0.5, p.z as f64 + 0.5);
+    vein.sort_by(|a, b| {
+        center(a)
+            .distance_squared(origin)
+            .total_cmp(&center(b).distance_squared(origin))
+    });
+    vein
+}
+
+#[derive(XrossClass)]
+pub struct VeinFinder {
+    connectivity: Connectivity,
+    max_blocks: usize,
+    groups: FxHashMap<i32, usize>,
+    group_count: usize,
+    vein: Vec<u64>,
+}
+
+impl Default for VeinFinder {
+    fn default() -> Self {
+        Self {
+            connectivity: Connectivity::default(),
+            max_blocks: 64,
+            groups: FxHashMap::default(),
+            group_count: 0,
+            vein: Vec::new(),
+        }
+    }
+}
+
+#[xross_methods]
+impl VeinFinder {
+    #[xross_new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `6`, `18` or `26`. Anything else falls back to `6`.
+    #[xross_method(critical)]
+    pub fn set_connectivity(&mut self, neighbors: i32) {
+        self.connectivity = Connectivity::from_neighbors(neighbors);
+    }
+
+    #[xross_method(critical)]
+    pub fn set_max_blocks(&mut self, max_blocks: i32) {
+        self.max_blocks = max_blocks.max(1) as usize;
+    }
+
+    /// Ids in one group are treated as the same block (e.g. an ore and its deepslate variant).
+    #[xross_method]
+    pub fn add_id_group(&mut self, ids: &[i32]) {
+        for &id in ids {
+            self.groups.insert(id, self.group_count);
+        }
+        self.group_count += 1;
+    }
+
+    #[xross_method(critical)]
+    pub fn clear_id_groups(&mut self) {
+        self.groups.clear();
+        self.group_count = 0;
+    }
+
+    /// Collects the vein containing `(x, y, z)`, ordered by distance to the eye
+    /// position. Returns the number of blocks found.
+    #[xross_method(panicable)]
+    pub fn find(&mut self, x: i32, y: i32, z: i32, eye_x: f64, eye_y: f64, eye_z: f64) -> usize {
+        let view = WorldSnapshot::instance().view();
+        let vein = find_vein(
+            &view,
+            BlockPos::new(x, y, z),
+            DVec3::new(eye_x, eye_y, eye_z),
+            self.connectivity,
+            self.max_blocks,
+            &self.groups,
+        );
+        self.vein = vein.iter().map(|p| p.pack()).collect();
+        self.vein.len()
+    }
+
+    /// Break order packed like `BlockPos::asLong`.
+    #[xross_method(critical)]
+    pub fn get_vein_ptr(&self) -> *const u64 {
+        self.vein.as_ptr()
+    }
+
+    #[xross_method(critical)]
+    pub fn get_vein_size(&self) -> usize {
+        self.vein.len()
+    }
+
+    #[xross_method(panicable)]
+    pub fn build_mesh(&self, mesh: &mut InfiniteMesh, color: i32) {
+        let mut generator = BlockMeshGenerator::new();
+        for &packed in &self.vein {
+            let pos = BlockPos::unpack(packed);
+            generator.add_block(pos.x, pos.y, pos.z, color);
+        }
+        generator.generate();
+        mesh.extend_from_block_mesh(&BlockMesh::from_generator(&generator));
+    }
+}
+
+impl VeinFinder {
+    pub fn vein(&self) -> impl Iterator<Item = BlockPos> + '_ {
+        self.vein.iter().map(|&p| BlockPos::unpack(p))
+    }
+}